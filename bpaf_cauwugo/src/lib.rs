@@ -18,7 +18,7 @@ macro_rules! pass_arg {
 
 macro_rules! pass_pos {
     ($cmd:ident, $field:expr) => {
-        for v in $field {
+        if let Some(v) = $field {
             $cmd.arg(v);
         }
     };
@@ -216,6 +216,7 @@ impl ToTokens for PostParse {
             PostParse::Map { f, .. } => quote!(map(#f)),
             PostParse::Optional { .. } => quote!(optional()),
             PostParse::Parse { f, .. } => quote!(parse(#f)),
+            PostParse::ParseStyled { f, .. } => quote!(parse_styled(#f)),
             PostParse::Strict { .. } => quote!(strict()),
         }
         .to_tokens(tokens);
@@ -235,6 +236,8 @@ impl ToTokens for PostDecor {
             PostDecor::Last { .. } => quote!(last()),
             PostDecor::GroupHelp { doc, .. } => quote!(group_help(#doc)),
             PostDecor::Guard { check, msg, .. } => quote!(guard(#check, #msg)),
+            PostDecor::GuardWith { check, msg, .. } => quote!(guard_with(#check, #msg)),
+            PostDecor::Heading { heading, .. } => quote!(heading(#heading)),
             PostDecor::Hide { .. } => quote!(hide()),
             PostDecor::CustomUsage { usage, .. } => quote!(custom_usage(#usage)),
             PostDecor::HideUsage { .. } => quote!(hide_usage()),
@@ -254,6 +257,7 @@ pub(crate) enum PostParse {
     Map { span: Span, f: Box<Expr> },
     Optional { span: Span },
     Parse { span: Span, f: Box<Expr> },
+    ParseStyled { span: Span, f: Box<Expr> },
     Strict { span: Span },
 }
 impl PostParse {
@@ -268,6 +272,7 @@ impl PostParse {
             | Self::Map { span, .. }
             | Self::Optional { span }
             | Self::Parse { span, .. }
+            | Self::ParseStyled { span, .. }
             | Self::Strict { span } => *span,
         }
     }
@@ -313,6 +318,15 @@ pub(crate) enum PostDecor {
         check: Box<Expr>,
         msg: Box<Expr>,
     },
+    GuardWith {
+        span: Span,
+        check: Box<Expr>,
+        msg: Box<Expr>,
+    },
+    Heading {
+        span: Span,
+        heading: LitStr,
+    },
     Hide {
         span: Span,
     },
@@ -337,6 +351,8 @@ impl PostDecor {
             | Self::FallbackWith { span, .. }
             | Self::GroupHelp { span, .. }
             | Self::Guard { span, .. }
+            | Self::GuardWith { span, .. }
+            | Self::Heading { span, .. }
             | Self::Hide { span }
             | Self::CustomUsage { span, .. }
             | Self::HideUsage { span } => *span,
@@ -475,6 +491,9 @@ impl PostParse {
         } else if kw == "parse" {
             let f = parse_arg(input)?;
             Self::Parse { span, f }
+        } else if kw == "parse_styled" {
+            let f = parse_arg(input)?;
+            Self::ParseStyled { span, f }
         } else if kw == "strict" {
             Self::Strict { span }
         } else if kw == "some" {
@@ -516,6 +535,12 @@ impl PostDecor {
         } else if kw == "guard" {
             let (check, msg) = parse_arg2(input)?;
             Self::Guard { span, check, msg }
+        } else if kw == "guard_with" {
+            let (check, msg) = parse_arg2(input)?;
+            Self::GuardWith { span, check, msg }
+        } else if kw == "heading" {
+            let heading = parse_lit_str(input)?;
+            Self::Heading { span, heading }
         } else if kw == "hide" {
             Self::Hide { span }
         } else if kw == "hide_usage" {
@@ -207,6 +207,18 @@ fn derive_field_guard_const() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn derive_field_guard_with() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(guard_with(positive, not_positive))]
+        number: usize
+    };
+    let output = quote! {
+        ::bpaf::long("number").argument::<usize>("ARG").guard_with(positive, not_positive)
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn derive_help() {
     let input: NamedField = parse_quote! {
@@ -222,6 +234,77 @@ fn derive_help() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn derive_markdown_help() {
+    let input: NamedField = parse_quote! {
+        /// Read config from `--config` or <FILE>, *not* from `stdin`
+        config: String
+    };
+    let output = quote! {
+        ::bpaf::long("config")
+            .help(&[
+                ("Read config from ", ::bpaf::doc::Style::Text),
+                ("--config", ::bpaf::doc::Style::Literal),
+                (" or ", ::bpaf::doc::Style::Text),
+                ("FILE", ::bpaf::doc::Style::Metavar),
+                (", ", ::bpaf::doc::Style::Text),
+                ("not", ::bpaf::doc::Style::Emphasis),
+                (" from ", ::bpaf::doc::Style::Text),
+                ("stdin", ::bpaf::doc::Style::Literal)
+            ])
+            .argument::<String>("ARG")
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
+#[test]
+fn derive_markdown_blocks() {
+    let input: NamedField = parse_quote! {
+        /// Output format, one of:
+        /// - `json`
+        /// - `yaml`,
+        ///   default
+        ///
+        /// ```
+        /// app --format json
+        /// ```
+        format: String
+    };
+    let output = quote! {
+        ::bpaf::long("format")
+            .help({
+                let mut doc = ::bpaf::doc::Doc::default();
+                doc.text("Output format, one of:");
+                doc.bullet_list([
+                    ::bpaf::doc::Doc::from(&[("json", ::bpaf::doc::Style::Literal)][..]),
+                    ::bpaf::doc::Doc::from(&[
+                        ("yaml", ::bpaf::doc::Style::Literal),
+                        (", default", ::bpaf::doc::Style::Text)
+                    ][..])
+                ]);
+                doc.text("\n\n");
+                doc.code_block("app --format json");
+                doc
+            })
+            .argument::<String>("ARG")
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
+#[test]
+fn derive_markdown_literal_chars() {
+    let input: NamedField = parse_quote! {
+        /// Multiply 2 * 3, see Vec<T> and snake_case_name, escaped \*
+        number: usize
+    };
+    let output = quote! {
+        ::bpaf::long("number")
+            .help("Multiply 2 * 3, see Vec<T> and snake_case_name, escaped *")
+            .argument::<usize>("ARG")
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn map_requires_explicit_parser() {
     let input: NamedField = parse_quote! {
@@ -686,6 +769,22 @@ fn hide_and_group_help() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn heading_field() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(argument("HOST"), heading("Network:"))]
+        /// Server to connect to
+        host: String
+    };
+    let output = quote! {
+        ::bpaf::long("host")
+            .help("Server to connect to")
+            .argument::<String>("HOST")
+            .heading("Network:")
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn any_field_1() {
     let input: NamedField = parse_quote! {
@@ -892,6 +991,20 @@ fn argument_with_manual_parse() {
     assert_eq!(input.to_token_stream().to_string(), output.to_string());
 }
 
+#[test]
+fn argument_with_styled_parse() {
+    let input: NamedField = parse_quote! {
+        #[bpaf(argument::<String>("N"), parse_styled(twice_the_num))]
+        number: u32
+    };
+    let output = quote! {
+        ::bpaf::long("number")
+            .argument::<String>("N")
+            .parse_styled(twice_the_num)
+    };
+    assert_eq!(input.to_token_stream().to_string(), output.to_string());
+}
+
 #[test]
 fn optional_external_strange() {
     let input: NamedField = parse_quote! {
@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
     Expr, Result,
@@ -15,7 +15,7 @@ impl ToTokens for Help {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             Help::Custom(c) => c.to_tokens(tokens),
-            Help::Doc(d) => d.to_tokens(tokens),
+            Help::Doc(d) => doc_tokens(d).to_tokens(tokens),
         }
     }
 }
@@ -31,3 +31,242 @@ impl Parse for Help {
         Ok(Help::Custom(input.parse()?))
     }
 }
+
+/// Style of a text fragment, mirrors `bpaf::doc::Style`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Style {
+    Text,
+    Emphasis,
+    Literal,
+    Metavar,
+}
+
+impl ToTokens for Style {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Style::Text => quote!(::bpaf::doc::Style::Text),
+            Style::Emphasis => quote!(::bpaf::doc::Style::Emphasis),
+            Style::Literal => quote!(::bpaf::doc::Style::Literal),
+            Style::Metavar => quote!(::bpaf::doc::Style::Metavar),
+        }
+        .to_tokens(tokens);
+    }
+}
+
+type Spans = Vec<(String, Style)>;
+
+/// Doc comment with markdown subset parsed
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Block {
+    Text(Spans),
+    List {
+        numbered: bool,
+        items: Vec<Spans>,
+    },
+    /// Fenced code block along with its info string, if any
+    Code {
+        lang: String,
+        code: String,
+    },
+}
+
+/// Doc comment as a string literal if it contains no markup, `&[(&str, Style)]` if it only
+/// uses inline markup or an expression that builds a `Doc`
+fn doc_tokens(doc: &str) -> TokenStream {
+    let blocks = parse_markdown(doc);
+    match blocks.as_slice() {
+        [] => quote!(""),
+        [Block::Text(spans)] if spans.len() == 1 && spans[0].1 == Style::Text => {
+            spans[0].0.to_token_stream()
+        }
+        [Block::Text(spans)] => spans_tokens(spans),
+        _ => {
+            let blocks = blocks.iter().map(|block| match block {
+                Block::Text(spans) => {
+                    let calls = spans.iter().map(|(text, style)| match style {
+                        Style::Text => quote!(doc.text(#text);),
+                        Style::Emphasis => quote!(doc.emphasis(#text);),
+                        Style::Literal => quote!(doc.literal(#text);),
+                        Style::Metavar => {
+                            quote!(doc.doc(&::bpaf::doc::Doc::from(&[(#text, #style)][..]));)
+                        }
+                    });
+                    quote!(#(#calls)*)
+                }
+                Block::List { numbered, items } => {
+                    let items = items.iter().map(|spans| {
+                        let spans = spans_tokens(spans);
+                        quote!(::bpaf::doc::Doc::from(#spans[..]))
+                    });
+                    if *numbered {
+                        quote!(doc.numbered_list([#(#items),*]);)
+                    } else {
+                        quote!(doc.bullet_list([#(#items),*]);)
+                    }
+                }
+                Block::Code { lang, code } if lang.is_empty() => quote!(doc.code_block(#code);),
+                Block::Code { lang, code } => quote!(doc.code_block_lang(#lang, #code);),
+            });
+            quote!({
+                let mut doc = ::bpaf::doc::Doc::default();
+                #(#blocks)*
+                doc
+            })
+        }
+    }
+}
+
+fn spans_tokens(spans: &[(String, Style)]) -> TokenStream {
+    let spans = spans.iter().map(|(text, style)| quote!((#text, #style)));
+    quote!(&[#(#spans),*])
+}
+
+/// List item marker: `- `, `* ` or `1. `
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some((false, rest));
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ") {
+            return Some((true, rest));
+        }
+    }
+    None
+}
+
+/// Split a doc comment into text, lists and fenced code blocks
+///
+/// Text keeps the original line breaks so paragraphs are handled the same way as in plain
+/// help messages, a blank line after a list or a code block starts a new paragraph
+pub(crate) fn parse_markdown(doc: &str) -> Vec<Block> {
+    fn flush(text: &mut String, res: &mut Vec<Block>) {
+        if !text.ends_with("\n\n") {
+            text.pop();
+        }
+        // blank line between two blocks is kept as a paragraph break
+        if !text.trim().is_empty() || text == "\n\n" {
+            res.push(Block::Text(parse_inline(text)));
+        }
+        text.clear();
+    }
+
+    let mut res = Vec::new();
+    let mut text = String::new();
+    let mut lines = doc.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(info) = line.strip_prefix("```") {
+            flush(&mut text, &mut res);
+            let lang = info
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_owned();
+            let mut code = Vec::new();
+            for line in lines.by_ref() {
+                if line.starts_with("```") {
+                    break;
+                }
+                code.push(line);
+            }
+            let code = code.join("\n");
+            res.push(Block::Code { lang, code });
+        } else if let Some((numbered, item)) = list_item(line) {
+            flush(&mut text, &mut res);
+            let mut items = vec![item.to_owned()];
+            while let Some(&next) = lines.peek() {
+                match list_item(next) {
+                    Some((n, item)) if n == numbered => items.push(item.to_owned()),
+                    None if next.starts_with(' ') && !next.trim().is_empty() => {
+                        let last = items.last_mut().expect("list is never empty");
+                        last.push(' ');
+                        last.push_str(next.trim());
+                    }
+                    _ => break,
+                }
+                lines.next();
+            }
+            let items = items.iter().map(|i| parse_inline(i)).collect();
+            res.push(Block::List { numbered, items });
+        } else {
+            if line.is_empty() && text.is_empty() && !res.is_empty() {
+                // paragraph break after a list or a code block
+                text.push('\n');
+            }
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    flush(&mut text, &mut res);
+    res
+}
+
+/// Split text into fragments styled with inline code, emphasis and `<METAVAR>` markup
+pub(crate) fn parse_inline(input: &str) -> Spans {
+    fn push(res: &mut Spans, text: &str, style: Style) {
+        match res.last_mut() {
+            Some((prev, prev_style)) if *prev_style == style => prev.push_str(text),
+            _ => res.push((text.to_owned(), style)),
+        }
+    }
+
+    let mut res = Vec::new();
+    let mut rest = input;
+    let mut prev: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        let boundary = !matches!(prev, Some(c) if c.is_alphanumeric());
+        let after = &rest[c.len_utf8()..];
+        let styled = match c {
+            '\\' if after.starts_with(|c| "\\`*_<>".contains(c)) => {
+                push(&mut res, &after[..1], Style::Text);
+                prev = after.chars().next();
+                rest = &after[1..];
+                continue;
+            }
+            '`' => after
+                .find('`')
+                .filter(|&end| end > 0)
+                .map(|end| (&after[..end], Style::Literal, 1, 1)),
+            '*' if after.starts_with('*') => after[1..]
+                .find("**")
+                .filter(|&end| end > 0)
+                .map(|end| (&after[1..end + 1], Style::Emphasis, 2, 2)),
+            '*' => after
+                .find('*')
+                .filter(|&end| end > 0 && !after.starts_with(' '))
+                .map(|end| (&after[..end], Style::Emphasis, 1, 1)),
+            '_' if boundary => after
+                .find('_')
+                .filter(|&end| {
+                    end > 0
+                        && !after.starts_with(' ')
+                        && !after[end + 1..].starts_with(char::is_alphanumeric)
+                })
+                .map(|end| (&after[..end], Style::Emphasis, 1, 1)),
+            '<' if boundary => after
+                .find('>')
+                .filter(|&end| {
+                    let name = &after[..end];
+                    name.starts_with(|c: char| c.is_ascii_uppercase())
+                        && name.chars().all(|c| {
+                            c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-'
+                        })
+                })
+                .map(|end| (&after[..end], Style::Metavar, 1, 1)),
+            _ => None,
+        };
+        match styled {
+            Some((text, style, open, close)) => {
+                push(&mut res, text, style);
+                prev = text.chars().last();
+                rest = &rest[open + text.len() + close..];
+            }
+            None => {
+                push(&mut res, &rest[..c.len_utf8()], Style::Text);
+                prev = Some(c);
+                rest = after;
+            }
+        }
+    }
+    res
+}
@@ -1,7 +1,7 @@
 use crate::{
     attrs::PostDecor,
     help::Help,
-    utils::{parse_arg, parse_opt_arg},
+    utils::{parse_arg, parse_arg2, parse_opt_arg},
 };
 use quote::{quote, ToTokens};
 use syn::{
@@ -29,6 +29,7 @@ pub(crate) struct OptionsCfg {
     pub(crate) header: Option<Help>,
     pub(crate) usage: Option<Box<Expr>>,
     pub(crate) version: Option<Box<Expr>>,
+    pub(crate) examples: Vec<(LitStr, Help)>,
 }
 
 #[derive(Debug, Default)]
@@ -50,33 +51,6 @@ pub(crate) enum Mode {
     },
 }
 
-#[derive(Debug)]
-pub(crate) enum HelpMsg {
-    Lit(String),
-    Custom(Box<Expr>),
-}
-
-impl From<String> for HelpMsg {
-    fn from(value: String) -> Self {
-        Self::Lit(value)
-    }
-}
-
-impl From<Box<Expr>> for HelpMsg {
-    fn from(value: Box<Expr>) -> Self {
-        Self::Custom(value)
-    }
-}
-
-impl ToTokens for HelpMsg {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        match self {
-            HelpMsg::Lit(l) => l.to_tokens(tokens),
-            HelpMsg::Custom(l) => l.to_tokens(tokens),
-        }
-    }
-}
-
 #[derive(Debug)]
 pub(crate) struct TopInfo {
     /// Should visibility for generated function to be inherited?
@@ -226,6 +200,9 @@ impl Parse for TopInfo {
             } else if kw == "usage" {
                 let usage = parse_arg(input)?;
                 with_options(&kw, options.as_mut(), |opt| opt.usage = Some(usage))?;
+            } else if kw == "example" {
+                let example = parse_arg2(input)?;
+                with_options(&kw, options.as_mut(), |opt| opt.examples.push(example))?;
             } else if kw == "group_help" {
                 let group_help = parse_arg(input)?;
                 with_parser(&kw, parser.as_mut(), |opt| {
@@ -336,6 +313,9 @@ impl Parse for Ed {
                 attrs.push(EAttr::Header(parse_arg(input)?));
             } else if kw == "footer" {
                 attrs.push(EAttr::Footer(parse_arg(input)?));
+            } else if kw == "example" {
+                let (command, explanation) = parse_arg2(input)?;
+                attrs.push(EAttr::Example(command, explanation));
             } else if kw == "env" {
                 attrs.push(EAttr::Env(parse_arg(input)?));
             } else {
@@ -372,6 +352,7 @@ pub(crate) enum EAttr {
     Descr(Help),
     Header(Help),
     Footer(Help),
+    Example(LitStr, Help),
     Usage(Box<Expr>),
     Env(Box<Expr>),
     ToOptions,
@@ -388,6 +369,7 @@ impl ToTokens for EAttr {
             Self::Descr(d) => quote!(descr(#d)),
             Self::Header(d) => quote!(header(#d)),
             Self::Footer(d) => quote!(footer(#d)),
+            Self::Example(c, d) => quote!(example(#c, #d)),
             Self::Usage(u) => quote!(usage(#u)),
             Self::Env(e) => quote!(env(#e)),
             Self::Hide => quote!(hide()),
@@ -190,6 +190,7 @@ impl ToTokens for Top {
                     descr,
                     footer,
                     header,
+                    examples,
                 } = options;
 
                 let version = version.as_ref().map(|v| quote!(.version(#v)));
@@ -197,6 +198,7 @@ impl ToTokens for Top {
                 let descr = descr.as_ref().map(|v| quote!(.descr(#v)));
                 let footer = footer.as_ref().map(|v| quote!(.footer(#v)));
                 let header = header.as_ref().map(|v| quote!(.header(#v)));
+                let examples = examples.iter().map(|(c, d)| quote!(.example(#c, #d)));
 
                 let CommandCfg {
                     name,
@@ -220,6 +222,7 @@ impl ToTokens for Top {
                         #descr
                         #header
                         #footer
+                        #(#examples)*
                         #usage
                         .command(#name)
                         #(#short)*
@@ -238,6 +241,7 @@ impl ToTokens for Top {
                     descr,
                     footer,
                     header,
+                    examples,
                 } = options;
                 let body = match cargo_helper {
                     Some(cargo) => quote!(::bpaf::cargo_helper(#cargo, #body)),
@@ -249,6 +253,7 @@ impl ToTokens for Top {
                 let descr = descr.as_ref().map(|v| quote!(.descr(#v)));
                 let footer = footer.as_ref().map(|v| quote!(.footer(#v)));
                 let header = header.as_ref().map(|v| quote!(.header(#v)));
+                let examples = examples.iter().map(|(c, d)| quote!(.example(#c, #d)));
                 quote! {
                     #vis fn #generate() -> ::bpaf::OptionParser<#ty> {
                         #[allow(unused_imports)]
@@ -260,6 +265,7 @@ impl ToTokens for Top {
                         #descr
                         #header
                         #footer
+                        #(#examples)*
                         #usage
                     }
                 }
@@ -459,6 +465,16 @@ impl ParsedEnumBranch {
                     }
                 }
                 EAttr::Adjacent | EAttr::Hide => attrs.push(attr),
+                EAttr::Example(..) => {
+                    // keep examples in the order they were written
+                    if let Some(o) = attrs.iter().position(|i| matches!(i, EAttr::ToOptions)) {
+                        let mut at = o + 1;
+                        while matches!(attrs.get(at), Some(EAttr::Example(..))) {
+                            at += 1;
+                        }
+                        attrs.insert(at, attr);
+                    }
+                }
                 EAttr::Header(_) | EAttr::Footer(_) | EAttr::Descr(_) => {
                     if let Some(o) = attrs.iter().position(|i| matches!(i, EAttr::ToOptions)) {
                         attrs.insert(o + 1, attr);
@@ -97,6 +97,56 @@ fn top_struct_options1() {
     assert_eq!(top.to_token_stream().to_string(), expected.to_string());
 }
 
+#[test]
+fn options_with_examples() {
+    let top: Top = parse_quote! {
+        #[bpaf(options, example("-v", "Verbose output"), example("-q", "Quiet output"))]
+        struct Opt {}
+    };
+
+    let expected = quote! {
+        fn opt() -> ::bpaf::OptionParser<Opt> {
+            #[allow (unused_imports)]
+            use ::bpaf::Parser;
+                {
+                    ::bpaf::construct!(Opt {})
+                }
+                .to_options()
+                .example("-v", "Verbose output")
+                .example("-q", "Quiet output")
+        }
+    };
+
+    assert_eq!(top.to_token_stream().to_string(), expected.to_string());
+}
+
+#[test]
+fn enum_command_with_examples() {
+    let input: Top = parse_quote! {
+        enum Opt {
+            #[bpaf(command, example("--fast", "Go fast"), example("--slow", "Go slow"))]
+            Run { fast: bool },
+        }
+    };
+
+    let expected = quote! {
+        fn opt() -> impl ::bpaf::Parser<Opt> {
+            #[allow(unused_imports)]
+            use ::bpaf::Parser;
+            {
+                let fast = ::bpaf::long("fast").switch();
+                ::bpaf::construct!(Opt::Run { fast ,})
+            }
+            .to_options()
+            .example("--fast", "Go fast")
+            .example("--slow", "Go slow")
+            .command("run")
+        }
+    };
+
+    assert_eq!(input.to_token_stream().to_string(), expected.to_string());
+}
+
 #[test]
 fn options_with_custom_usage() {
     let top: Top = parse_quote! {
@@ -281,7 +331,12 @@ fn enum_markdownish() {
                 ::bpaf::construct!(Opt::Opt { field ,})
             }
             .to_options()
-            .footer("Examples:\n\n```sh\ncargo 1\ncargo 2\n```")
+            .footer({
+                let mut doc = ::bpaf::doc::Doc::default();
+                doc.text("Examples:\n\n");
+                doc.code_block_lang("sh", "cargo 1\ncargo 2");
+                doc
+            })
             .descr("Make a tree")
             .header("x")
             .command("opt")
@@ -114,6 +114,7 @@ fn check_to_snake_case() {
 ///   ["foo", "bar"] => ["foo\nbar"]
 /// - single empty lines are stripped and used to represent logical blocks:
 ///   ["foo", "bar", "", "baz"] => ["foo\nbar", "baz"]
+///
 /// strip single empty lines,
 pub(crate) struct LineIter<'a> {
     strings: std::str::Lines<'a>,
@@ -186,7 +187,7 @@ impl Iterator for LineIter<'_> {
 }
 
 #[cfg(test)]
-fn split(input: &str) -> LineIter {
+fn split(input: &str) -> LineIter<'_> {
     LineIter::from(input)
 }
 
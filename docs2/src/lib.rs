@@ -14,6 +14,7 @@ fn write_updated(new_val: String, path: impl AsRef<std::path::Path>) -> std::io:
         .write(true)
         .read(true)
         .create(true)
+        .truncate(false)
         .open(path)?;
     let mut current_val = String::new();
     file.read_to_string(&mut current_val)?;
@@ -194,6 +194,7 @@ pub fn write_updated(new_val: String, path: impl AsRef<std::path::Path>) -> std:
         .write(true)
         .read(true)
         .create(true)
+        .truncate(false)
         .open(path)?;
     let mut current_val = String::new();
     file.read_to_string(&mut current_val)?;
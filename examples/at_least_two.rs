@@ -8,7 +8,7 @@ fn main() {
     let opt = short('f')
         .req_flag(())
         .many()
-        .guard(|x| x.len() >= 2, "at least two arguments are required")
+        .at_least(2)
         .to_options()
         .run();
 
@@ -29,26 +29,24 @@ pub struct Options {
 
 // Parses -user xxx
 fn user() -> impl Parser<Option<String>> {
-    // match only literal "-user"
-    let tag = literal("-user").anywhere();
-    let value = positional("USER").help("User name");
-    construct!(tag, value)
-        .adjacent()
-        .map(|pair| pair.1)
+    // single dash long name, "-user"
+    single_dash("user")
+        .help("User name")
+        .argument("USER")
         .optional()
 }
 
 // parsers -exec xxx yyy zzz ;
 fn exec() -> impl Parser<Option<Vec<OsString>>> {
-    let tag = literal("-exec")
+    let tag = single_dash("exec")
         .help("for every file find finds execute a separate shell command")
-        .anywhere();
+        .req_flag(());
 
-    let item = any::<OsString, _, _>("ITEM", |s| (s != ";").then_some(s))
+    let item = any::<OsString, _, _>("ITEM", |s| if s != ";" { Some(s) } else { None })
         .help("command with its arguments, find will replace {} with a file name")
         .many();
 
-    let endtag = any::<String, _, _>(";", |s| (s == ";").then_some(()))
+    let endtag = any::<String, _, _>(";", |s| if s == ";" { Some(()) } else { None })
         .help("anything after literal \";\" will be considered a regular option again");
 
     construct!(tag, item, endtag)
@@ -72,7 +70,7 @@ fn perm() -> impl Parser<Option<Perm>> {
         Ok(perms)
     }
 
-    let tag = literal("-mode").anywhere();
+    let tag = single_dash("mode").req_flag(());
 
     // `any` here is used to parse an arbitrary string that can also start with dash (-)
     // regular positional parser won't work here
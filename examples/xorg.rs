@@ -9,29 +9,9 @@ pub struct Options {
     extensions: Vec<(String, bool)>,
 }
 
-// matches literal name prefixed with - or +
-fn toggle_options(meta: &'static str, name: &'static str, help: &'static str) -> impl Parser<bool> {
-    any(meta, move |s: String| {
-        if let Some(suf) = s.strip_prefix('+') {
-            (suf == name).then_some(true)
-        } else if let Some(suf) = s.strip_prefix('-') {
-            (suf == name).then_some(false)
-        } else {
-            None
-        }
-    })
-    .help(help)
-    .anywhere()
-}
-
-// matches literal +ext and -ext followed by extension name
+// matches +ext and -ext followed by extension name
 fn extension() -> impl Parser<(String, bool)> {
-    let state = any("(+|-)ext", |s: String| match s.as_str() {
-        "-ext" => Some(false),
-        "+ext" => Some(true),
-        _ => None,
-    })
-    .anywhere();
+    let state = single_dash("ext").toggle();
 
     let name = positional::<String>("EXT")
         .help("Extension to enable or disable, see documentation for the full list");
@@ -39,9 +19,15 @@ fn extension() -> impl Parser<(String, bool)> {
 }
 
 pub fn options() -> OptionParser<Options> {
-    let backing = toggle_options("(+|-)backing", "backing", "Set backing status").fallback(false);
-    let xinerama =
-        toggle_options("(+|-)xinerama", "xinerama", "Set Xinerama status").fallback(true);
+    // +backing and -backing
+    let backing = single_dash("backing")
+        .help("Set backing status")
+        .toggle()
+        .fallback(false);
+    let xinerama = single_dash("xinerama")
+        .help("Set Xinerama status")
+        .toggle()
+        .fallback(true);
     let turbo = short('t')
         .long("turbo")
         .help("Engage the turbo mode")
@@ -1,9 +1,12 @@
 use std::ffi::{OsStr, OsString};
 
+use crate::args::{KnownNames, NameMatch};
+
 /// Preprocessed command line argument
 ///
 /// [`OsString`] in Short/Long correspond to orignal command line item used for errors
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub(crate) enum Arg {
     /// short flag: `-f`
     ///
@@ -14,6 +17,10 @@ pub(crate) enum Arg {
     /// bool tells if it looks like --key=val or not
     Long(String, bool, OsString),
 
+    /// single dash long name: `-name` or a toggle `+name`
+    /// bool tells if it starts with `+`
+    Single(String, bool, OsString),
+
     /// "val" part of --key=val -k=val -kval
     ArgWord(OsString),
 
@@ -35,6 +42,7 @@ impl Arg {
         match self {
             Arg::Short(_, _, s)
             | Arg::Long(_, _, s)
+            | Arg::Single(_, _, s)
             | Arg::ArgWord(s)
             | Arg::Word(s)
             | Arg::PosWord(s) => s.as_ref(),
@@ -44,14 +52,22 @@ impl Arg {
     pub(crate) fn match_short(&self, val: char) -> bool {
         match self {
             Arg::Short(s, _, _) => *s == val,
-            Arg::ArgWord(_) | Arg::Long(_, _, _) | Arg::Word(_) | Arg::PosWord(_) => false,
+            Arg::ArgWord(_)
+            | Arg::Long(_, _, _)
+            | Arg::Single(_, _, _)
+            | Arg::Word(_)
+            | Arg::PosWord(_) => false,
         }
     }
 
-    pub(crate) fn match_long(&self, val: &str) -> bool {
+    pub(crate) fn match_long(&self, val: &str, names: Option<&NameMatch>) -> bool {
         match self {
-            Arg::Long(s, _, _) => *s == val,
-            Arg::Short(_, _, _) | Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
+            Arg::Long(s, _, _) => *s == val || names.map_or(false, |n| n.same(s, val)),
+            Arg::Short(_, _, _)
+            | Arg::Single(_, _, _)
+            | Arg::ArgWord(_)
+            | Arg::Word(_)
+            | Arg::PosWord(_) => false,
         }
     }
 }
@@ -77,8 +93,12 @@ impl Arg {
 impl std::fmt::Display for Arg {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Arg::Short(s, _, os) if is_slash(os) => write!(f, "/{}", s),
+            Arg::Long(l, _, os) if is_slash(os) => write!(f, "/{}", l),
             Arg::Short(s, _, _) => write!(f, "-{}", s),
             Arg::Long(l, _, _) => write!(f, "--{}", l),
+            Arg::Single(l, false, _) => write!(f, "-{}", l),
+            Arg::Single(l, true, _) => write!(f, "+{}", l),
             Arg::ArgWord(w) | Arg::Word(w) | Arg::PosWord(w) => {
                 write!(f, "{}", w.to_string_lossy())
             }
@@ -92,6 +112,57 @@ pub(crate) enum ArgType {
     Long,
 }
 
+/// Check if [`OsString`] is one of the known single dash long names: `-name`, or `+name` for
+/// names declared as toggles
+///
+/// Happens before [`split_os_argument`] since otherwise `-name` would be split into
+/// a group of short flags or a short flag with an argument
+pub(crate) fn split_single(input: &OsString, names: &KnownNames) -> Option<Arg> {
+    let s = input.to_str()?;
+    let (name, plus) = match s.strip_prefix('-') {
+        Some(name) => (name, false),
+        None => (s.strip_prefix('+')?, true),
+    };
+    let known = if plus { &names.toggles } else { &names.singles };
+    if known.contains(&name) {
+        Some(Arg::Single(name.to_owned(), plus, input.clone()))
+    } else {
+        None
+    }
+}
+
+fn is_slash(os: &OsStr) -> bool {
+    os.to_str().map_or(false, |s| s.starts_with('/'))
+}
+
+/// Split a slash style named item: `/f`, `/flag`, `/flag:value` or `/flag=value`
+///
+/// Only known names are split, anything else such as `/usr/bin` stays a positional item.
+/// Returns the name and an optional value
+pub(crate) fn split_slash(input: &OsString, names: &KnownNames) -> Option<(Arg, Option<Arg>)> {
+    let body = input.to_str()?.strip_prefix('/')?;
+    let (name, value) = match body.find(|c| c == ':' || c == '=') {
+        Some(ix) => (&body[..ix], Some(&body[ix + 1..])),
+        None => (body, None),
+    };
+    let mut chars = name.chars();
+    let arg = match (chars.next(), chars.next()) {
+        (Some(c), None) if names.short_flags.contains(&c) || names.short_args.contains(&c) => {
+            Arg::Short(c, value.is_some(), input.clone())
+        }
+        _ if names.longs.contains(&name)
+            || names
+                .relaxed
+                .as_ref()
+                .map_or(false, |r| r.any_long_matches(&names.longs, name)) =>
+        {
+            Arg::Long(name.to_owned(), value.is_some(), input.clone())
+        }
+        _ => return None,
+    };
+    Some((arg, value.map(|v| Arg::ArgWord(v.into()))))
+}
+
 /// split [`OsString`] into argument specific bits
 ///
 /// takes a possibly non-utf8 string looking like "--name=value" and splits it into bits:
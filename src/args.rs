@@ -1,12 +1,13 @@
-use std::ffi::OsString;
+use std::{borrow::Cow, ffi::OsString};
 
 pub(crate) use crate::arg::*;
 use crate::{
     error::{Message, MissingItem},
+    info::Info,
     item::Item,
     meta_help::Metavar,
     parsers::NamedArg,
-    Error,
+    Error, Meta, OptionStyle,
 };
 
 /// All currently present command line parameters with some extra metainfo
@@ -180,6 +181,213 @@ impl ItemState {
     }
 }
 
+/// Names used to split the command line into items, collected from all the parsers
+#[derive(Debug, Default)]
+pub(crate) struct KnownNames {
+    /// short names of flags, used for disambiguation
+    pub(crate) short_flags: Vec<char>,
+    /// short names of arguments, used for disambiguation
+    pub(crate) short_args: Vec<char>,
+    /// single dash long names: `-name`
+    pub(crate) singles: Vec<&'static str>,
+    /// single dash long names that also accept `+name`
+    pub(crate) toggles: Vec<&'static str>,
+    /// long names, used to split slash style options
+    pub(crate) longs: Vec<&'static str>,
+    /// long and single dash names of arguments, used to find the first positional item
+    pub(crate) long_args: Vec<&'static str>,
+    /// command names of the top level parser, they don't stop option parsing in options
+    /// first mode
+    pub(crate) commands: Vec<CommandNames>,
+    /// environment variable names, used to look for typos
+    pub(crate) envs: Vec<&'static str>,
+    /// everything after the first positional item is strictly positional
+    pub(crate) options_first: bool,
+    /// prefix style for named items
+    pub(crate) style: OptionStyle,
+    /// relaxed name matching rules of the top level parser, if any, used for slash style names
+    /// and for command names in options first mode
+    pub(crate) relaxed: Option<NameMatch>,
+}
+
+/// Name of a command along with the names of its own subcommands
+#[derive(Debug)]
+pub(crate) struct CommandNames {
+    pub(crate) name: &'static str,
+    pub(crate) short: Option<char>,
+    pub(crate) commands: Vec<CommandNames>,
+    /// relaxed name matching rules inside of the command, if any
+    pub(crate) relaxed: Option<NameMatch>,
+}
+
+impl CommandNames {
+    /// Check if `word` is this command, `relaxed` are the rules of the parser that contains it
+    fn matches(&self, word: &str, relaxed: Option<&NameMatch>) -> bool {
+        let mut tmp = [0u8; 4];
+        self.name == word
+            || self.short.map(|s| &*s.encode_utf8(&mut tmp)) == Some(word)
+            || relaxed.map_or(false, |r| r.command_matches(self.name, word))
+    }
+}
+
+/// Long flag and command names available to the current parser and the rules to match them
+///
+/// Present in [`State`] only when any of the relaxed matching rules are enabled, see
+/// [`OptionParser::abbreviations`](crate::OptionParser::abbreviations),
+/// [`OptionParser::ignore_case`](crate::OptionParser::ignore_case) and
+/// [`OptionParser::underscores_as_dashes`](crate::OptionParser::underscores_as_dashes)
+#[derive(Debug, Default)]
+pub(crate) struct NameMatch {
+    longs: Vec<&'static str>,
+    commands: Vec<&'static str>,
+    /// accept unique prefixes
+    abbreviations: bool,
+    /// `--Verbose` is the same as `--verbose`
+    ignore_case: bool,
+    /// `--dry_run` is the same as `--dry-run`
+    underscores: bool,
+}
+
+impl NameMatch {
+    /// Create name matcher for a parser, rules enabled for the parent parser stay enabled
+    pub(crate) fn new(meta: &Meta, info: &Info, parent: Option<&Self>) -> Option<Self> {
+        let abbreviations = info.abbreviations || parent.map_or(false, |p| p.abbreviations);
+        let ignore_case = info.ignore_case || parent.map_or(false, |p| p.ignore_case);
+        let underscores = info.underscores_as_dashes || parent.map_or(false, |p| p.underscores);
+        if !(abbreviations || ignore_case || underscores) {
+            return None;
+        }
+        let mut res = Self {
+            abbreviations,
+            ignore_case,
+            underscores,
+            ..Self::default()
+        };
+        meta.collect_longs(&mut res.longs, &mut res.commands);
+        res.longs.extend(info.help_arg.long.iter().copied());
+        if info.version.is_some() {
+            res.longs.extend(info.version_arg.long.iter().copied());
+        }
+        Some(res)
+    }
+
+    /// Bring the name to a form used for comparison
+    fn normalize<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let mut res = Cow::Borrowed(name);
+        if self.ignore_case && res.chars().any(char::is_uppercase) {
+            res = Cow::Owned(res.to_lowercase());
+        }
+        if self.underscores && res.contains('_') {
+            res = Cow::Owned(res.replace('_', "-"));
+        }
+        res
+    }
+
+    /// Check if two names are the same
+    pub(crate) fn same(&self, a: &str, b: &str) -> bool {
+        self.normalize(a) == self.normalize(b)
+    }
+
+    /// Check if user input `val` matches one of long `names`
+    pub(crate) fn long_matches(&self, names: &[&'static str], val: &str) -> bool {
+        self.matches(&self.longs, names, val)
+    }
+
+    /// Check if user input `val` matches one of `longs` or is a prefix to any of them,
+    /// ambiguous prefixes are reported later by the parser
+    pub(crate) fn any_long_matches(&self, longs: &[&'static str], val: &str) -> bool {
+        self.matches(longs, longs, val)
+    }
+
+    /// Check if user input `val` matches command `name`
+    pub(crate) fn command_matches(&self, name: &str, val: &str) -> bool {
+        self.matches(&self.commands, &[name], val)
+    }
+
+    /// `val` matches one of `names` exactly or, if enabled, is a prefix to them and not to
+    /// anything else in `all`
+    fn matches(&self, all: &[&str], names: &[&str], val: &str) -> bool {
+        let val = self.normalize(val);
+        if val.is_empty() {
+            return false;
+        }
+        let starts = |n: &&str| self.normalize(n).starts_with(val.as_ref());
+        names.iter().any(|n| self.normalize(n) == val)
+            || (self.abbreviations
+                && names.iter().any(starts)
+                && all.iter().all(|n| !starts(n) || names.contains(n)))
+    }
+
+    /// If `arg` is a prefix to more than one known name - return all of them
+    pub(crate) fn ambiguity(&self, arg: &Arg) -> Option<Vec<String>> {
+        if !self.abbreviations {
+            return None;
+        }
+        let (names, val, dashes) = match arg {
+            Arg::Long(l, _, _) => (&self.longs, l.as_str(), "--"),
+            Arg::Word(w) => (&self.commands, w.to_str()?, ""),
+            _ => return None,
+        };
+        let val = self.normalize(val);
+        if val.is_empty() || names.iter().any(|n| self.normalize(n) == val) {
+            return None;
+        }
+        let mut found = names
+            .iter()
+            .filter(|n| self.normalize(n).starts_with(val.as_ref()))
+            .map(|n| format!("{}{}", dashes, n))
+            .collect::<Vec<_>>();
+        found.sort();
+        found.dedup();
+        if found.len() > 1 {
+            Some(found)
+        } else {
+            None
+        }
+    }
+}
+
+/// Check if `arg` is a name of a global `item`, `Some(true)` if it is followed by a value
+fn global_matches(item: &Item, arg: &Arg, names: Option<&NameMatch>) -> Option<bool> {
+    let (longs, shorts, singles, value) = match item {
+        Item::Flag {
+            longs,
+            shorts,
+            singles,
+            ..
+        } => (longs, shorts, singles, false),
+        Item::Argument {
+            longs,
+            shorts,
+            singles,
+            ..
+        } => (longs, shorts, singles, true),
+        Item::Any { .. } | Item::Positional { .. } | Item::Command { .. } => return None,
+    };
+    let matches = match arg {
+        Arg::Short(s, _, _) => shorts.contains(s),
+        Arg::Long(_, _, _) => longs.iter().any(|l| arg.match_long(l, names)),
+        Arg::Single(s, false, _) => singles.contains(&s.as_str()),
+        Arg::Single(_, true, _) | Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
+    };
+    if matches {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Check if last item is a name that expects a separate value
+fn expects_value(last: Option<&Arg>, names: &KnownNames) -> bool {
+    match last {
+        Some(Arg::Short(s, false, _)) => names.short_args.contains(s),
+        Some(Arg::Long(l, false, _) | Arg::Single(l, false, _)) => {
+            names.long_args.iter().any(|n| n == l)
+        }
+        _ => false,
+    }
+}
+
 fn disambiguate_short(
     mut os: OsString,
     short: String,
@@ -247,7 +455,10 @@ mod inner {
 
     use crate::{error::Message, Args};
 
-    use super::{split_os_argument, Arg, ArgType, ItemState};
+    use super::{
+        split_os_argument, split_single, split_slash, Arg, ArgType, ItemState, KnownNames,
+    };
+    use crate::OptionStyle;
     #[derive(Clone, Debug)]
     #[doc(hidden)]
     pub struct State {
@@ -268,6 +479,53 @@ mod inner {
         /// path to current command, "deeper" parser should win in or_else branches
         pub(crate) path: Vec<String>,
 
+        /// long and command names available in the current parser, present only when
+        /// relaxed name matching is enabled
+        pub(crate) names: Option<Rc<super::NameMatch>>,
+
+        /// prefix style used for named items, affects errors and completion
+        pub(crate) style: OptionStyle,
+
+        /// options are accepted only before the first positional item, affects help
+        pub(crate) options_first: bool,
+
+        /// help message layout of the top level parser
+        pub(crate) layout: crate::info::HelpLayout,
+
+        /// error message presentation of the top level parser
+        pub(crate) error_style: crate::info::ErrorStyle,
+
+        /// number of errors to report at once, top level parser decides
+        pub(crate) max_errors: usize,
+
+        /// failures collected after the first one when several errors are reported at once,
+        /// dropped along with the state if the first failure is handled
+        pub(crate) errors: Vec<Message>,
+
+        /// bounded repetitions that stopped at the maximum: item, min and max,
+        /// used to report leftover items with the expected count
+        pub(crate) bounded: Vec<(Box<crate::Meta>, usize, usize)>,
+
+        /// suggested replacements for values that failed to parse, by item index
+        pub(crate) value_hints: Vec<(usize, String)>,
+
+        /// completion candidates for a value at this index, collected when looking for a
+        /// replacement to suggest
+        #[cfg(feature = "autocomplete")]
+        pub(crate) candidates: Option<(usize, Vec<String>)>,
+
+        /// indices of values consumed by parsers with a completion function, only those
+        /// values are worth looking for a replacement
+        #[cfg(feature = "autocomplete")]
+        pub(crate) completed: Vec<usize>,
+
+        /// help and version names of the current parser, [`pass_through`](crate::pass_through)
+        /// leaves them alone
+        pub(crate) reserved: Rc<[crate::parsers::NamedArg]>,
+
+        /// global named items from the current and the parent parsers
+        pub(crate) globals: Rc<[crate::item::Item]>,
+
         #[cfg(feature = "autocomplete")]
         comp: Option<crate::complete_gen::Complete>,
 
@@ -312,13 +570,15 @@ mod inner {
         #[allow(clippy::too_many_lines)] // it's relatively simple.
         pub(crate) fn construct(
             args: Args,
-            short_flags: &[char],
-            short_args: &[char],
+            names: &KnownNames,
             err: &mut Option<Message>,
         ) -> State {
             let mut items = Vec::new();
             let mut pos_only = false;
             let mut double_dash_marker = None;
+            // commands available in the innermost command seen so far and the rules to match them
+            let mut commands = names.commands.as_slice();
+            let mut relaxed = names.relaxed.as_ref();
 
             #[cfg(feature = "autocomplete")]
             let mut comp_scanner = crate::complete_run::ArgScanner {
@@ -337,14 +597,34 @@ mod inner {
                     continue;
                 }
 
-                match split_os_argument(&os) {
+                // -name and +name
+                if let Some(arg) = split_single(&os, names) {
+                    items.push(arg);
+                    continue;
+                }
+
+                // /f, /flag and /flag:value
+                if names.style != OptionStyle::Dash {
+                    if let Some((arg, value)) = split_slash(&os, names) {
+                        items.push(arg);
+                        items.extend(value);
+                        continue;
+                    }
+                }
+
+                let split = if names.style == OptionStyle::Slash {
+                    None
+                } else {
+                    split_os_argument(&os)
+                };
+                match split {
                     // -f and -fbar, but also -vvvvv
                     Some((ArgType::Short, short, None)) => {
                         if let Some(msg) = super::disambiguate_short(
                             os,
                             short,
-                            short_flags,
-                            short_args,
+                            &names.short_flags,
+                            &names.short_args,
                             &mut items,
                         ) {
                             *err = Some(msg);
@@ -369,6 +649,25 @@ mod inner {
                         if os == "--" {
                             double_dash_marker = Some(items.len());
                             pos_only = true;
+                        } else if names.options_first && !super::expects_value(items.last(), names)
+                        {
+                            let word = os.to_str();
+                            match commands
+                                .iter()
+                                .find(|c| word.map_or(false, |w| c.matches(w, relaxed)))
+                            {
+                                // subcommand, options that follow belong to it
+                                Some(cmd) => {
+                                    commands = &cmd.commands;
+                                    relaxed = cmd.relaxed.as_ref();
+                                }
+                                // first positional item, everything after it is positional too
+                                None => {
+                                    items.push(Arg::Word(os));
+                                    pos_only = true;
+                                    continue;
+                                }
+                            }
                         }
                         items.push(if pos_only {
                             Arg::PosWord(os)
@@ -407,6 +706,21 @@ mod inner {
                 items: items.into(),
                 current: None,
                 path,
+                names: None,
+                style: names.style,
+                options_first: names.options_first,
+                layout: crate::info::HelpLayout::default(),
+                error_style: crate::info::ErrorStyle::Short,
+                max_errors: 1,
+                errors: Vec::new(),
+                bounded: Vec::new(),
+                value_hints: Vec::new(),
+                #[cfg(feature = "autocomplete")]
+                candidates: None,
+                #[cfg(feature = "autocomplete")]
+                completed: Vec::new(),
+                reserved: Rc::new([]),
+                globals: Rc::new([]),
                 #[cfg(feature = "autocomplete")]
                 comp,
             }
@@ -531,7 +845,7 @@ mod inner {
             start..start + span_size
         }
 
-        pub(crate) fn ranges(&self) -> ArgRangesIter {
+        pub(crate) fn ranges(&self) -> ArgRangesIter<'_> {
             ArgRangesIter { args: self, cur: 0 }
         }
 
@@ -630,7 +944,7 @@ impl State {
     pub(crate) fn take_flag(&mut self, named: &NamedArg) -> bool {
         if let Some((ix, _)) = self
             .items_iter()
-            .find(|arg| named.matches_arg(arg.1, false))
+            .find(|arg| named.matches_arg(arg.1, false, self.names.as_deref()))
         {
             self.remove(ix);
             true
@@ -639,6 +953,18 @@ impl State {
         }
     }
 
+    /// Get a toggle: `+name` / `-name`
+    ///
+    /// Returns `None` if toggle isn't present, `Some(true)` for `+name`
+    pub(crate) fn take_toggle(&mut self, named: &NamedArg) -> Option<bool> {
+        let (ix, plus) = self.items_iter().find_map(|(ix, arg)| match arg {
+            Arg::Single(s, plus, _) if named.single.contains(&s.as_str()) => Some((ix, *plus)),
+            _ => None,
+        })?;
+        self.remove(ix);
+        Some(plus)
+    }
+
     /// get a short or long arguments
     ///
     /// Returns Ok(None) if flag isn't present
@@ -651,7 +977,7 @@ impl State {
     ) -> Result<Option<OsString>, Error> {
         let (key_ix, _arg) = match self
             .items_iter()
-            .find(|arg| named.matches_arg(arg.1, adjacent))
+            .find(|arg| named.matches_arg(arg.1, adjacent, self.names.as_deref()))
         {
             Some(v) => v,
             None => return Ok(None),
@@ -708,7 +1034,11 @@ impl State {
         if let Some((ix, Arg::Word(w) | Arg::Short(_, _, w) | Arg::Long(_, false, w))) =
             self.items_iter().next()
         {
-            if w == word {
+            let relaxed = || match (&self.names, w.to_str()) {
+                (Some(names), Some(w)) => names.command_matches(word, w),
+                _ => false,
+            };
+            if w == word || relaxed() {
                 self.remove(ix);
                 self.current = Some(ix);
                 return true;
@@ -718,6 +1048,118 @@ impl State {
         false
     }
 
+    /// First item in the current scope that isn't one of the `globals` or their value
+    ///
+    /// Parent parsers consume their global items once the subparser is done
+    pub(crate) fn first_non_global(&self, globals: &[Item]) -> Option<usize> {
+        let mut value = None;
+        for (ix, arg) in self.items_iter() {
+            if value == Some(ix) {
+                continue;
+            }
+            match globals
+                .iter()
+                .find_map(|g| global_matches(g, arg, self.names.as_deref()))
+            {
+                Some(true) => value = Some(ix + 1),
+                Some(false) => {}
+                None => return Some(ix),
+            }
+        }
+        None
+    }
+
+    /// Record every item left unconsumed as a separate failure, used when several errors are
+    /// reported at once
+    ///
+    /// Values attached to an unexpected name or following it are not reported on their own
+    pub(crate) fn collect_unconsumed(&mut self, globals: &[Item]) {
+        let mut value = None;
+        let mut name = None;
+        let mut unexpected = Vec::new();
+        for (ix, arg) in self.items_iter() {
+            if value == Some(ix) {
+                continue;
+            }
+            match globals
+                .iter()
+                .find_map(|g| global_matches(g, arg, self.names.as_deref()))
+            {
+                Some(true) => value = Some(ix + 1),
+                Some(false) => {}
+                None => match arg {
+                    Arg::ArgWord(_) => {}
+                    Arg::Word(_) if name.map(|n| n + 1) == Some(ix) => {}
+                    Arg::Short(..) | Arg::Long(..) | Arg::Single(..) => {
+                        name = Some(ix);
+                        unexpected.push(Message::Unconsumed(ix));
+                    }
+                    Arg::Word(_) | Arg::PosWord(_) => unexpected.push(Message::Unconsumed(ix)),
+                },
+            }
+        }
+        self.errors.extend(unexpected);
+    }
+
+    /// Copy of the state with a value at `ix` replaced by an empty string
+    ///
+    /// Completion functions usually only produce candidates that start with the value typed so
+    /// far, an empty value gets all of them
+    #[cfg(feature = "autocomplete")]
+    pub(crate) fn blank_value(&self, ix: usize) -> Option<State> {
+        let mut items = self.items.to_vec();
+        items[ix] = match items.get(ix)? {
+            Arg::ArgWord(_) => Arg::ArgWord(OsString::new()),
+            Arg::Word(_) => Arg::Word(OsString::new()),
+            Arg::PosWord(_) => Arg::PosWord(OsString::new()),
+            Arg::Short(..) | Arg::Long(..) | Arg::Single(..) => return None,
+        };
+        let mut state = self.clone();
+        state.items = items.into();
+        Some(state)
+    }
+
+    /// Keep parsing the rest of [`construct!`](crate::construct!) fields after a failure
+    ///
+    /// The first failure is returned once all the fields are done, later ones are kept on the
+    /// side. Without several errors reported at once the first failure is returned right away.
+    #[doc(hidden)]
+    pub fn collect_error<T>(
+        &mut self,
+        res: Result<T, Error>,
+        failed: &mut bool,
+    ) -> Result<Result<T, Error>, Error> {
+        let err = match &res {
+            Ok(_) => return Ok(res),
+            Err(Error(err)) => err,
+        };
+
+        // for completion bpaf needs to observe all the failures in a branch
+        #[cfg(feature = "autocomplete")]
+        if self.is_comp() {
+            return Ok(res);
+        }
+
+        if *failed {
+            self.errors.push(err.clone());
+            Ok(res)
+        } else if self.max_errors > 1
+            && !matches!(err, Message::ParseFailure(_) | Message::Subcommand(_, _))
+        {
+            *failed = true;
+            Ok(res)
+        } else {
+            res.map(Ok)
+        }
+    }
+
+    /// Find an item in the current scope that is a prefix to several names at once
+    pub(crate) fn ambiguous_prefix(&self) -> Option<(usize, Vec<String>)> {
+        let names = self.names.as_ref()?;
+        self.items_iter()
+            .find_map(|(ix, arg)| Some((ix, names.ambiguity(arg)?)))
+    }
+
     #[cfg(test)]
     pub(crate) fn peek(&self) -> Option<&Arg> {
         self.items_iter().next().map(|x| x.1)
@@ -736,7 +1178,7 @@ mod tests {
         fn from(value: &'static [&'static str; N]) -> Self {
             let args = Args::from(value);
             let mut msg = None;
-            let res = State::construct(args, &[], &[], &mut msg);
+            let res = State::construct(args, &KnownNames::default(), &mut msg);
             if let Some(err) = &msg {
                 panic!("Couldn't construct state: {:?}/{:?}", err, res);
             }
@@ -766,7 +1208,14 @@ mod tests {
     fn multiple_short_flags() {
         let args = Args::from(&["-vvv"]);
         let mut err = None;
-        let mut a = State::construct(args, &['v'], &[], &mut err);
+        let mut a = State::construct(
+            args,
+            &KnownNames {
+                short_flags: vec!['v'],
+                ..KnownNames::default()
+            },
+            &mut err,
+        );
         assert!(a.take_flag(&short('v')));
         assert!(a.take_flag(&short('v')));
         assert!(a.take_flag(&short('v')));
@@ -890,7 +1339,14 @@ mod tests {
     fn ambiguity_towards_flag() {
         let args = Args::from(&["-abc"]);
         let mut err = None;
-        let mut a = State::construct(args, &['a', 'b', 'c'], &[], &mut err);
+        let mut a = State::construct(
+            args,
+            &KnownNames {
+                short_flags: vec!['a', 'b', 'c'],
+                ..KnownNames::default()
+            },
+            &mut err,
+        );
 
         assert!(a.take_flag(&short('a')));
         assert!(a.take_flag(&short('b')));
@@ -901,7 +1357,14 @@ mod tests {
     fn ambiguity_towards_argument() {
         let args = Args::from(&["-abc"]);
         let mut err = None;
-        let mut a = State::construct(args, &[], &['a'], &mut err);
+        let mut a = State::construct(
+            args,
+            &KnownNames {
+                short_args: vec!['a'],
+                ..KnownNames::default()
+            },
+            &mut err,
+        );
 
         let r = a.take_arg(&short('a'), false, M).unwrap().unwrap();
         assert_eq!(r, "bc");
@@ -911,7 +1374,15 @@ mod tests {
     fn ambiguity_towards_error() {
         let args = Args::from(&["-abc"]);
         let mut err = None;
-        let _a = State::construct(args, &['a', 'b', 'c'], &['a'], &mut err);
+        let _a = State::construct(
+            args,
+            &KnownNames {
+                short_flags: vec!['a', 'b', 'c'],
+                short_args: vec!['a'],
+                ..KnownNames::default()
+            },
+            &mut err,
+        );
         assert!(err.is_some());
     }
 
@@ -4,6 +4,7 @@ use crate::{
     meta_help::{HelpItem, HelpItems},
 };
 use crate::{
+    info::UsageStyle,
     item::{Item, ShortLong},
     Meta,
 };
@@ -12,7 +13,11 @@ mod console;
 mod html;
 #[cfg(feature = "docgen")]
 mod manpage;
+#[cfg(feature = "docgen")]
+mod regions;
 mod splitter;
+mod width;
+pub(crate) use width::str_width;
 
 pub(crate) use self::console::Color;
 #[cfg(feature = "docgen")]
@@ -87,11 +92,27 @@ impl Doc {
         self.write_meta(meta.0, for_usage);
     }
 
+    /// Mark the definition list term that starts next as documenting an item
+    pub(crate) fn anchor(&mut self, kind: &'static str, name: &str, names: Vec<String>) {
+        let anchor = Anchor {
+            kind,
+            name: name.to_owned(),
+            names,
+        };
+        self.anchors.push((self.tokens.len(), anchor));
+    }
+
     /// Append a `Doc` to [`Doc`]
     ///
     /// See [`Doc`] for usage examples
     pub fn doc(&mut self, buf: &Doc) {
         self.tokens.push(Token::BlockStart(Block::InlineBlock));
+        let offset = self.tokens.len();
+        self.anchors.extend(
+            buf.anchors
+                .iter()
+                .map(|(ix, anchor)| (ix + offset, anchor.clone())),
+        );
         self.tokens.extend(&buf.tokens);
         self.payload.push_str(&buf.payload);
         self.tokens.push(Token::BlockEnd(Block::InlineBlock));
@@ -127,17 +148,177 @@ impl Doc {
 
         self.tokens.push(Token::BlockEnd(Block::InlineBlock));
     }
+
+    /// Append a bulleted list to [`Doc`], one item per element
+    ///
+    /// ```rust
+    /// # use bpaf::doc::*;
+    /// let mut doc = Doc::default();
+    /// doc.text("Supported formats:");
+    /// doc.bullet_list(["json", "yaml"]);
+    /// assert_eq!(doc.monochrome(true), "Supported formats:\n  - json\n  - yaml\n");
+    /// ```
+    pub fn bullet_list<I, D>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<Doc>,
+    {
+        self.write_list(Block::UnnumberedList, items);
+    }
+
+    /// Append a numbered list to [`Doc`], one item per element
+    ///
+    /// ```rust
+    /// # use bpaf::doc::*;
+    /// let mut doc = Doc::default();
+    /// doc.numbered_list(["Build", "Deploy"]);
+    /// assert_eq!(doc.monochrome(true), "  1. Build\n  2. Deploy\n");
+    /// ```
+    pub fn numbered_list<I, D>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<Doc>,
+    {
+        self.write_list(Block::NumberedList, items);
+    }
+
+    fn write_list<I, D>(&mut self, list: Block, items: I)
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<Doc>,
+    {
+        self.token(Token::BlockStart(list));
+        for item in items {
+            self.token(Token::BlockStart(Block::ItemBody));
+            self.doc(&item.into());
+            self.token(Token::BlockEnd(Block::ItemBody));
+        }
+        self.token(Token::BlockEnd(list));
+    }
+
+    /// Append a block of preformatted text to [`Doc`]
+    ///
+    /// Lines are kept as is without wrapping, such as a shell session or a config file sample
+    ///
+    /// ```rust
+    /// # use bpaf::doc::*;
+    /// let mut doc = Doc::default();
+    /// doc.text("Sample config:");
+    /// doc.code_block("[server]\nport = 8080");
+    /// assert_eq!(doc.monochrome(true), "Sample config:\n\n    [server]\n    port = 8080\n");
+    /// ```
+    pub fn code_block(&mut self, code: &str) {
+        self.token(Token::BlockStart(Block::CodeBlock));
+        self.text(code);
+        self.token(Token::BlockEnd(Block::CodeBlock));
+    }
+
+    /// Append a block of preformatted text in a given language to [`Doc`]
+    ///
+    /// Same as [`code_block`](Doc::code_block), markdown and html output keep the language
+    /// so the block can be highlighted, console and manpage output ignore it
+    ///
+    /// ```rust
+    /// # use bpaf::doc::*;
+    /// let mut doc = Doc::default();
+    /// doc.code_block_lang("sh", "cargo build");
+    /// assert_eq!(doc.monochrome(true), "    cargo build\n");
+    /// assert_eq!(doc.render_markdown(true), "```sh\ncargo build\n```\n\n");
+    /// ```
+    pub fn code_block_lang(&mut self, lang: &str, code: &str) {
+        self.token(Token::BlockStart(Block::CodeBlock));
+        self.token(Token::BlockStart(Block::CodeLang));
+        self.text(lang);
+        self.token(Token::BlockEnd(Block::CodeLang));
+        self.text(code);
+        self.token(Token::BlockEnd(Block::CodeBlock));
+    }
+
+    /// Append a two column table to [`Doc`]
+    ///
+    /// In console output columns are aligned the same way as items in `--help`, markdown and
+    /// html use the first row as a header
+    ///
+    /// ```rust
+    /// # use bpaf::doc::*;
+    /// let mut doc = Doc::default();
+    /// doc.table([("Level", "Meaning"), ("0", "quiet"), ("1", "verbose")]);
+    /// assert_eq!(
+    ///     doc.monochrome(true),
+    ///     "    Level  Meaning\n    0      quiet\n    1      verbose\n"
+    /// );
+    /// ```
+    pub fn table<I, A, B>(&mut self, rows: I)
+    where
+        I: IntoIterator<Item = (A, B)>,
+        A: Into<Doc>,
+        B: Into<Doc>,
+    {
+        self.token(Token::BlockStart(Block::Table));
+        for (left, right) in rows {
+            self.token(Token::BlockStart(Block::ItemTerm));
+            self.doc(&left.into());
+            self.token(Token::BlockEnd(Block::ItemTerm));
+            self.token(Token::BlockStart(Block::ItemBody));
+            self.doc(&right.into());
+            self.token(Token::BlockEnd(Block::ItemBody));
+        }
+        self.token(Token::BlockEnd(Block::Table));
+    }
+
+    /// Append a hyperlink to [`Doc`]
+    ///
+    /// Terminals with color support get a clickable [OSC 8] link, in monochrome output url is
+    /// printed after the text unless they are the same.
+    ///
+    /// [OSC 8]: https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
+    ///
+    /// ```rust
+    /// # use bpaf::doc::*;
+    /// let mut doc = Doc::default();
+    /// doc.text("See ");
+    /// doc.link("the manual", "https://example.com/manual");
+    /// assert_eq!(doc.monochrome(true), "See the manual <https://example.com/manual>");
+    /// ```
+    pub fn link(&mut self, text: &str, url: &str) {
+        self.token(Token::BlockStart(Block::Link));
+        self.token(Token::BlockStart(Block::LinkUrl));
+        self.text(url);
+        self.token(Token::BlockEnd(Block::LinkUrl));
+        self.text(text);
+        self.token(Token::BlockEnd(Block::Link));
+    }
 }
 
 impl Doc {
+    pub(crate) fn short_prefix(&self) -> &'static str {
+        if self.slash {
+            "/"
+        } else {
+            "-"
+        }
+    }
+
+    pub(crate) fn long_prefix(&self) -> &'static str {
+        if self.slash {
+            "/"
+        } else {
+            "--"
+        }
+    }
+
     pub(crate) fn write_shortlong(&mut self, name: &ShortLong) {
         match name {
             ShortLong::Short(s) => {
-                self.write_char('-', Style::Literal);
+                self.write_str(self.short_prefix(), Style::Literal);
                 self.write_char(*s, Style::Literal);
             }
             ShortLong::Long(l) | ShortLong::ShortLong(_, l) => {
-                self.write_str("--", Style::Literal);
+                self.write_str(self.long_prefix(), Style::Literal);
+                self.write_str(l, Style::Literal);
+            }
+            ShortLong::Single(l, toggle) => {
+                self.write_str(if *toggle { "(+|-)" } else { "-" }, Style::Literal);
                 self.write_str(l, Style::Literal);
             }
         }
@@ -160,18 +341,33 @@ impl Doc {
             Item::Flag {
                 name,
                 shorts: _,
+                longs: _,
+                singles: _,
                 env: _,
                 help: _,
+                global: _,
             } => self.write_shortlong(name),
             Item::Argument {
                 name,
                 shorts: _,
+                longs: _,
+                singles: _,
                 metavar,
                 env: _,
                 help: _,
+                global: _,
             } => {
                 self.write_shortlong(name);
-                self.write_char('=', Style::Text);
+                // single dash names can't have adjacent values
+                // and slash style names use `/name:value`
+                let sep = if let ShortLong::Single(..) = name {
+                    ' '
+                } else if self.slash {
+                    ':'
+                } else {
+                    '='
+                };
+                self.write_char(sep, Style::Text);
                 self.metavar(*metavar);
             }
             Item::Any {
@@ -184,7 +380,34 @@ impl Doc {
         }
     }
 
+    /// Cardinality of a bounded repetition in a regex-like notation: `{3}`, `{2,}`, `{,5}`, `{2,5}`
+    pub(crate) fn write_bounds(&mut self, min: usize, max: usize) {
+        let bounds = if min == max {
+            format!("{{{}}}", min)
+        } else if max == usize::MAX {
+            format!("{{{},}}", min)
+        } else if min == 0 {
+            format!("{{,{}}}", max)
+        } else {
+            format!("{{{},{}}}", min, max)
+        };
+        self.write_str(&bounds, Style::Text);
+    }
+
     pub(crate) fn write_meta(&mut self, meta: &Meta, for_usage: bool) {
+        self.write_normalized(&meta.normalized(for_usage));
+    }
+
+    /// Usage line for a parser, compact style collapses optional named items into `[OPTIONS]`
+    pub(crate) fn write_usage(&mut self, meta: &Meta, style: UsageStyle) {
+        let mut meta = meta.normalized(true);
+        if style == UsageStyle::Compact {
+            meta.collapse_options();
+        }
+        self.write_normalized(&meta);
+    }
+
+    fn write_normalized(&mut self, meta: &Meta) {
         fn go(meta: &Meta, f: &mut Doc) {
             match meta {
                 Meta::And(xs) => {
@@ -218,8 +441,15 @@ impl Doc {
                     go(m, f);
                     f.write_str("...", Style::Text);
                 }
+                Meta::Bounded(m, min, max) => {
+                    go(m, f);
+                    f.write_bounds(*min, *max);
+                }
 
-                Meta::Adjacent(m) | Meta::Subsection(m, _) | Meta::Suffix(m, _) => {
+                Meta::Adjacent(m)
+                | Meta::Subsection(m, _)
+                | Meta::Heading(m, _)
+                | Meta::Suffix(m, _) => {
                     go(m, f);
                 }
                 Meta::Skip => {} // => f.write_str("no parameters expected", Style::Text),
@@ -234,9 +464,8 @@ impl Doc {
             }
         }
 
-        let meta = meta.normalized(for_usage);
         self.token(Token::BlockStart(Block::Mono));
-        go(&meta, self);
+        go(meta, self);
         self.token(Token::BlockEnd(Block::Mono));
     }
 }
@@ -263,6 +492,7 @@ pub enum Style {
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[allow(dead_code)]
+#[allow(clippy::enum_variant_names)]
 pub(crate) enum Block {
     /// level 1 section header, block for separate command inside manpage, not used in --help
     Header,
@@ -305,6 +535,28 @@ pub(crate) enum Block {
 
     /// Monospaced font that goes around [`Meta`]
     Mono,
+
+    /// Bulleted list, items are [`ItemBody`](Block::ItemBody)
+    UnnumberedList,
+
+    /// Numbered list, items are [`ItemBody`](Block::ItemBody)
+    NumberedList,
+
+    /// Preformatted text, lines are kept as is
+    CodeBlock,
+
+    /// Language of a [`CodeBlock`](Block::CodeBlock), goes first inside of it
+    CodeLang,
+
+    /// Two column table, rows are pairs of [`ItemTerm`](Block::ItemTerm) and
+    /// [`ItemBody`](Block::ItemBody), the first row is a header
+    Table,
+
+    /// Hyperlink, starts with [`LinkUrl`](Block::LinkUrl) followed by the link text
+    Link,
+
+    /// Target of a [`Link`](Block::Link)
+    LinkUrl,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -326,6 +578,27 @@ pub struct Doc {
 
     /// string meta info tokens
     tokens: Vec<Token>,
+
+    /// render named items with slashes instead of dashes: `/f`, `/flag`
+    pub(crate) slash: bool,
+
+    /// start help on a new line after item names too long for the help column
+    pub(crate) two_line: bool,
+
+    /// html anchors for item terms by the index of the token that starts the term,
+    /// used when rendering linked markdown pages
+    pub(crate) anchors: Vec<(usize, Anchor)>,
+}
+
+/// Names of an item documented by a definition list term, see [`Doc::anchor`]
+#[derive(Debug, Clone)]
+pub(crate) struct Anchor {
+    /// item kind, becomes a prefix of the anchor: `flag`, `arg`, `cmd` or `pos`
+    pub(crate) kind: &'static str,
+    /// name used for the anchor: long name if present
+    pub(crate) name: String,
+    /// names as they are rendered: `-v`, `--verbose`, used to link term references
+    pub(crate) names: Vec<String>,
 }
 
 impl std::fmt::Display for Doc {
@@ -404,6 +677,12 @@ impl From<&str> for Doc {
     }
 }
 
+impl From<String> for Doc {
+    fn from(value: String) -> Self {
+        Doc::from(value.as_str())
+    }
+}
+
 impl Doc {
     //    #[cfg(test)]
     //    pub(crate) fn clear(&mut self) {
@@ -462,6 +741,16 @@ struct DocSection<'a> {
     path: Vec<String>,
     info: &'a Info,
     meta: &'a Meta,
+    /// Global items from the parent parsers, listed in help along with the own items
+    globals: Vec<Item>,
+}
+
+#[cfg(feature = "docgen")]
+impl DocSection<'_> {
+    /// Help and version items along with global items from the parent parsers
+    fn help_meta(&self) -> Meta {
+        self.info.help_meta(&self.globals)
+    }
 }
 
 #[cfg(feature = "docgen")]
@@ -471,25 +760,184 @@ fn extract_sections<'a>(
     path: &mut Vec<String>,
     sections: &mut Vec<DocSection<'a>>,
 ) {
-    sections.push(DocSection {
-        path: path.clone(),
-        info,
-        meta,
-    });
-    let mut hi = HelpItems::default();
-    hi.append_meta(meta);
-    for item in &hi.items {
-        if let HelpItem::Command {
-            name,
-            short: _,
-            help: _,
-            meta,
+    fn go<'a>(
+        meta: &'a Meta,
+        info: &'a Info,
+        path: &mut Vec<String>,
+        globals: &mut Vec<Item>,
+        sections: &mut Vec<DocSection<'a>>,
+    ) {
+        sections.push(DocSection {
+            path: path.clone(),
             info,
-        } = item
+            meta,
+            globals: globals.clone(),
+        });
+        let parent_globals = globals.len();
+        meta.collect_globals(globals);
+        let mut hi = HelpItems::default();
+        hi.append_meta(meta);
+        for item in hi.all() {
+            if let HelpItem::Command {
+                name,
+                short: _,
+                help: _,
+                meta,
+                info,
+            } = item
+            {
+                path.push((*name).to_string());
+                go(meta, info, path, globals, sections);
+                path.pop();
+            }
+        }
+        globals.truncate(parent_globals);
+    }
+    go(meta, info, path, &mut Vec::new(), sections);
+}
+
+#[cfg(feature = "docgen")]
+impl Doc {
+    /// Sections that follow the options in generated documentation
+    ///
+    /// EXIT STATUS, ENVIRONMENT with variables used by items in `sections`, EXAMPLES, custom
+    /// sections from `info` and SEE ALSO that starts with `see_also` links, if any. Custom
+    /// sections named EXIT STATUS or ENVIRONMENT replace generated ones, `generated` set to
+    /// `false` skips the rest of them
+    fn write_extra_sections(
+        &mut self,
+        info: &Info,
+        generated: bool,
+        sections: &[DocSection],
+        see_also: &Doc,
+    ) {
+        const EXIT_STATUS: &str = "EXIT STATUS";
+        const ENVIRONMENT: &str = "ENVIRONMENT";
+        let is_custom = |name: &str| {
+            info.sections
+                .iter()
+                .any(|(n, _)| n.eq_ignore_ascii_case(name))
+        };
+
+        if is_custom(EXIT_STATUS) {
+            self.write_custom_sections(info, EXIT_STATUS);
+        } else if generated {
+            self.write_header(EXIT_STATUS);
+            self.token(Token::BlockStart(Block::DefinitionList));
+            for (code, descr) in [
+                (
+                    "0",
+                    "Successful run, also after printing help or version information",
+                ),
+                ("1", "Failed to parse the command line"),
+            ] {
+                self.token(Token::BlockStart(Block::ItemTerm));
+                self.literal(code);
+                self.token(Token::BlockEnd(Block::ItemTerm));
+                self.token(Token::BlockStart(Block::ItemBody));
+                self.text(descr);
+                self.token(Token::BlockEnd(Block::ItemBody));
+            }
+            self.token(Token::BlockEnd(Block::DefinitionList));
+        }
+
+        let mut envs: Vec<(&str, Option<&Doc>)> = Vec::new();
+        for section in sections {
+            let mut items = HelpItems::default();
+            items.append_meta(section.meta);
+            for item in items.all().copied() {
+                if let HelpItem::Flag {
+                    env: Some(env),
+                    help,
+                    ..
+                }
+                | HelpItem::Argument {
+                    env: Some(env),
+                    help,
+                    ..
+                } = item
+                {
+                    if !envs.iter().any(|(name, _)| *name == env) {
+                        envs.push((env, help));
+                    }
+                }
+            }
+        }
+        if is_custom(ENVIRONMENT) {
+            self.write_custom_sections(info, ENVIRONMENT);
+        } else if generated && !envs.is_empty() {
+            self.write_header(ENVIRONMENT);
+            self.token(Token::BlockStart(Block::DefinitionList));
+            for (env, help) in envs {
+                self.token(Token::BlockStart(Block::ItemTerm));
+                self.literal(env);
+                self.token(Token::BlockEnd(Block::ItemTerm));
+                if let Some(help) = help {
+                    self.token(Token::BlockStart(Block::ItemBody));
+                    self.doc(help);
+                    self.token(Token::BlockEnd(Block::ItemBody));
+                }
+            }
+            self.token(Token::BlockEnd(Block::DefinitionList));
+        }
+
+        if sections.iter().any(|s| !s.info.examples.is_empty()) {
+            self.write_header("EXAMPLES");
+            for section in sections {
+                self.write_examples(&section.path, &section.info.examples);
+            }
+        }
+
+        let is_see_also = |name: &str| name.eq_ignore_ascii_case("see also");
+        let is_replacement = |name: &str| {
+            name.eq_ignore_ascii_case(EXIT_STATUS) || name.eq_ignore_ascii_case(ENVIRONMENT)
+        };
+        for (name, body) in &info.sections {
+            if !(is_see_also(name) || is_replacement(name)) {
+                self.write_header(name);
+                self.token(Token::BlockStart(Block::Block));
+                self.doc(body);
+                self.token(Token::BlockEnd(Block::Block));
+            }
+        }
+
+        let mut custom = info
+            .sections
+            .iter()
+            .filter(|(name, _)| is_see_also(name))
+            .peekable();
+        if !see_also.is_empty() || custom.peek().is_some() {
+            self.write_header("SEE ALSO");
+            if !see_also.is_empty() {
+                self.token(Token::BlockStart(Block::Block));
+                self.doc(see_also);
+                self.token(Token::BlockEnd(Block::Block));
+            }
+            for (_, body) in custom {
+                self.token(Token::BlockStart(Block::Block));
+                self.doc(body);
+                self.token(Token::BlockEnd(Block::Block));
+            }
+        }
+    }
+
+    /// Custom sections from `info` with this name, ignoring case
+    fn write_custom_sections(&mut self, info: &Info, name: &str) {
+        for (name, body) in info
+            .sections
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
         {
-            path.push((*name).to_string());
-            extract_sections(meta, info, path, sections);
-            path.pop();
+            self.write_header(name);
+            self.token(Token::BlockStart(Block::Block));
+            self.doc(body);
+            self.token(Token::BlockEnd(Block::Block));
         }
     }
+
+    fn write_header(&mut self, name: &str) {
+        self.token(Token::BlockStart(Block::Header));
+        self.text(name);
+        self.token(Token::BlockEnd(Block::Header));
+    }
 }
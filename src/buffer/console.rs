@@ -32,6 +32,7 @@
 
 use super::{
     splitter::{split, Chunk},
+    width::str_width,
     Block, Doc, Skip, Token,
 };
 
@@ -149,7 +150,7 @@ impl Doc {
                 match token {
                     Token::Text { bytes, style: _ } => {
                         if in_term {
-                            current += self.payload[byte_pos..byte_pos + bytes].chars().count();
+                            current += str_width(&self.payload[byte_pos..byte_pos + bytes]);
                         }
                         byte_pos += bytes;
                     }
@@ -170,7 +171,6 @@ impl Doc {
         }
         let tabstop = tabstop + 4;
 
-        #[cfg(test)]
         let mut stack = Vec::new();
         let mut skip = Skip::default();
         let mut char_pos = 0;
@@ -184,6 +184,22 @@ impl Doc {
 
         let mut pending_margin = false;
 
+        // line inside of a code block has text from a previous fragment
+        let mut code_line_open = false;
+
+        // list item marker along with a column to print it at
+        let mut pending_marker: Option<(usize, String)> = None;
+        // item counters for nested lists
+        let mut counters: Vec<usize> = Vec::new();
+
+        // url collected from `LinkUrl`, opening escape sequence for a clickable link
+        // and position where link text starts
+        let mut url: Option<String> = None;
+        let mut pending_link: Option<String> = None;
+        // opening quote of a term reference, goes after the line break and margin, if any
+        let mut pending_tick = false;
+        let mut link: Option<(String, usize)> = None;
+
         for token in self.tokens.iter().copied() {
             match token {
                 Token::Text { bytes, style } => {
@@ -194,6 +210,57 @@ impl Doc {
                         continue;
                     }
 
+                    if let Some(url) = &mut url {
+                        url.push_str(input);
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeLang) {
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeBlock) {
+                        let margin = margins.last().copied().unwrap_or(0usize);
+                        for (ix, line) in input.lines().enumerate() {
+                            if ix > 0 {
+                                res.push('\n');
+                                char_pos = 0;
+                            } else if !code_line_open {
+                                if !res.is_empty() {
+                                    if !res.ends_with('\n') {
+                                        res.push('\n');
+                                    }
+                                    if pending_blank_line && !res.ends_with("\n\n") {
+                                        res.push('\n');
+                                    }
+                                }
+                                char_pos = 0;
+                            }
+                            pending_newline = false;
+                            pending_blank_line = false;
+                            pending_margin = false;
+                            code_line_open = true;
+                            if !line.is_empty() {
+                                if char_pos == 0 {
+                                    res.push_str(&PADDING[..margin]);
+                                    char_pos = margin;
+                                }
+                                #[cfg(feature = "color")]
+                                {
+                                    color.push_str(style, &mut res, line);
+                                }
+                                #[cfg(not(feature = "color"))]
+                                {
+                                    res.push_str(line);
+                                }
+                                char_pos += str_width(line);
+                            }
+                        }
+                        // fragments of the same line can use different styles
+                        code_line_open = !input.ends_with('\n');
+                        continue;
+                    }
+
                     for chunk in split(input) {
                         match chunk {
                             Chunk::Raw(s, w) => {
@@ -218,6 +285,15 @@ impl Doc {
                                     }
                                 }
 
+                                if let Some((col, marker)) = pending_marker.take() {
+                                    if let Some(missing) = col.checked_sub(char_pos) {
+                                        res.push_str(&PADDING[..missing]);
+                                        char_pos = col;
+                                    }
+                                    res.push_str(&marker);
+                                    char_pos += marker.len();
+                                }
+
                                 let mut pushed = 0;
                                 if let Some(missing) = margin.checked_sub(char_pos) {
                                     res.push_str(&PADDING[..missing]);
@@ -225,15 +301,30 @@ impl Doc {
                                     pushed = missing;
                                 }
                                 if pending_margin && char_pos >= MAX_TAB + 4 && pushed < 2 {
-                                    let missing = 2 - pushed;
-                                    res.push_str(&PADDING[..missing]);
-                                    char_pos += missing;
+                                    if self.two_line {
+                                        res.push('\n');
+                                        res.push_str(&PADDING[..margin]);
+                                        char_pos = margin;
+                                    } else {
+                                        let missing = 2 - pushed;
+                                        res.push_str(&PADDING[..missing]);
+                                        char_pos += missing;
+                                    }
                                 }
 
                                 pending_newline = false;
                                 pending_blank_line = false;
                                 pending_margin = false;
 
+                                if let Some(open) = pending_link.take() {
+                                    res.push_str(&open);
+                                }
+
+                                if std::mem::take(&mut pending_tick) {
+                                    res.push('`');
+                                    char_pos += 1;
+                                }
+
                                 #[cfg(feature = "color")]
                                 {
                                     color.push_str(style, &mut res, s);
@@ -262,7 +353,7 @@ impl Doc {
                     }
                 }
                 Token::BlockStart(block) => {
-                    #[cfg(test)]
+                    let parent = stack.last().copied();
                     stack.push(block);
                     let margin = margins.last().copied().unwrap_or(0usize);
 
@@ -280,8 +371,44 @@ impl Doc {
                             margins.push(margin + 4);
                         }
                         Block::ItemBody => {
-                            margins.push(margin + tabstop + 2);
-                            pending_margin = true;
+                            if let Some(list @ (Block::UnnumberedList | Block::NumberedList)) =
+                                parent
+                            {
+                                let marker = match counters.last_mut() {
+                                    Some(n) if list == Block::NumberedList => {
+                                        *n += 1;
+                                        format!("{}. ", n)
+                                    }
+                                    _ => String::from("- "),
+                                };
+                                margins.push(margin + 2 + marker.len());
+                                pending_marker = Some((margin + 2, marker));
+                                pending_newline = true;
+                            } else {
+                                margins.push(margin + tabstop + 2);
+                                pending_margin = true;
+                            }
+                        }
+                        Block::UnnumberedList | Block::NumberedList => {
+                            pending_newline = true;
+                            margins.push(margin);
+                            counters.push(0);
+                        }
+                        Block::CodeBlock => {
+                            pending_blank_line |= !skip.enabled();
+                            margins.push(margin + 4);
+                            code_line_open = false;
+                        }
+                        Block::Table => {
+                            pending_newline = true;
+                            margins.push(margin);
+                        }
+                        Block::Link | Block::CodeLang => {
+                            margins.push(margin);
+                        }
+                        Block::LinkUrl => {
+                            margins.push(margin);
+                            url = Some(String::new());
                         }
                         Block::InlineBlock => {
                             skip.push();
@@ -291,29 +418,65 @@ impl Doc {
                         }
                         Block::DefinitionList | Block::Meta | Block::Mono => {}
                         Block::TermRef => {
-                            if color == Color::Monochrome {
-                                res.push('`');
-                                char_pos += 1;
-                            }
+                            pending_tick = color == Color::Monochrome;
                         }
                     }
                 }
                 Token::BlockEnd(block) => {
+                    let _top = stack.pop();
                     #[cfg(test)]
-                    assert_eq!(stack.pop(), Some(block));
-
-                    margins.pop();
+                    assert_eq!(_top, Some(block));
+
+                    // inline blocks, definition lists and the like don't set a margin
+                    if !matches!(
+                        block,
+                        Block::InlineBlock
+                            | Block::DefinitionList
+                            | Block::Meta
+                            | Block::Mono
+                            | Block::TermRef
+                    ) {
+                        margins.pop();
+                    }
                     match block {
                         Block::ItemBody => {
                             pending_margin = false;
                         }
+                        Block::UnnumberedList | Block::NumberedList => {
+                            counters.pop();
+                            pending_newline = true;
+                        }
+                        Block::CodeBlock => {
+                            pending_blank_line |= !skip.enabled();
+                        }
+                        Block::Table => {
+                            pending_newline = true;
+                        }
+                        Block::LinkUrl => {
+                            let url = url.take().unwrap_or_default();
+                            if color != Color::Monochrome {
+                                pending_link = Some(format!("\x1b]8;;{}\x1b\\", url));
+                            }
+                            link = Some((url, res.len()));
+                        }
+                        Block::Link => {
+                            if let Some((url, start)) = link.take() {
+                                if color != Color::Monochrome {
+                                    res.push_str("\x1b]8;;\x1b\\");
+                                } else if res[start..].trim_start() != url {
+                                    res.push_str(&format!(" <{}>", url));
+                                    char_pos += str_width(&url) + 3;
+                                }
+                            }
+                        }
                         Block::Header
                         | Block::Section2
                         | Block::Section3
                         | Block::ItemTerm
                         | Block::DefinitionList
                         | Block::Meta
-                        | Block::Mono => {}
+                        | Block::Mono
+                        | Block::CodeLang => {}
                         Block::InlineBlock => {
                             skip.pop();
                         }
@@ -322,6 +485,9 @@ impl Doc {
                         }
                         Block::TermRef => {
                             if color == Color::Monochrome {
+                                if std::mem::take(&mut pending_tick) {
+                                    res.push('`');
+                                }
                                 res.push('`');
                                 char_pos += 1;
                             }
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use crate::{
     buffer::{
         splitter::{split, Chunk},
@@ -9,19 +11,22 @@ use crate::{
 #[cfg(feature = "docgen")]
 use crate::{
     buffer::{extract_sections, Info, Meta},
-    meta_help::render_help,
-    Parser,
+    meta_help::{render_help, HelpContext},
+    OptionStyle, Parser,
 };
 
 #[inline(never)]
 #[cfg(feature = "docgen")]
-fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
+pub(super) fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
     let mut sections = Vec::new();
     let root = meta;
     let mut path = vec![app];
     extract_sections(root, info, &mut path, &mut sections);
 
-    let mut buf = Doc::default();
+    let mut buf = Doc {
+        slash: info.option_style == OptionStyle::Slash,
+        ..Doc::default()
+    };
 
     if sections.len() > 1 {
         buf.token(Token::BlockStart(Block::Block));
@@ -43,7 +48,7 @@ fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
         }
     }
 
-    for section in sections {
+    for section in &sections {
         buf.token(Token::BlockStart(Block::Header));
         buf.text(&section.path.join(" ").to_string());
         buf.token(Token::BlockEnd(Block::Header));
@@ -52,11 +57,14 @@ fn collect_html(app: String, meta: &Meta, info: &Info) -> Doc {
             &section.path,
             section.info,
             section.meta,
-            &section.info.meta(),
+            &section.help_meta(),
             false,
+            HelpContext::from(info),
         );
         buf.doc(&b);
     }
+
+    buf.write_extra_sections(info, info.generated_sections, &sections, &Doc::default());
     buf
 }
 
@@ -72,6 +80,160 @@ impl<T> OptionParser<T> {
     pub fn render_markdown(&self, app: impl Into<String>) -> String {
         collect_html(app.into(), &self.inner.meta(), &self.info).render_markdown(true)
     }
+
+    /// Render command line documentation into a set of linked Markdown pages
+    ///
+    /// Suitable for `mdBook` or static site generators: app itself goes into `index.md` with
+    /// a summary of all the pages, every subcommand gets its own `index.md` in a directory
+    /// named after the command path: `build/index.md`, `build/deploy/index.md`. Characters
+    /// other than letters, digits and `-` in command names are escaped as `_` followed by hex
+    /// digits of their UTF-8 bytes. Pages start with breadcrumbs, subcommand names in command
+    /// lists link to their pages and every item gets an html anchor named after its kind and
+    /// long name: `flag-verbose`, `arg-file`, `cmd-build`. Returns a map from a file path
+    /// relative to the root page to the page contents.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let build = short('r').help("Build in release mode").switch()
+    ///     .to_options()
+    ///     .descr("Build the project")
+    ///     .command("build");
+    /// let pages = build.to_options().render_markdown_pages("app");
+    /// let names = pages.keys().map(String::as_str).collect::<Vec<_>>();
+    /// assert_eq!(names, ["build/index.md", "index.md"]);
+    /// assert!(pages["index.md"].contains("[**`build`**](build/index.md)"));
+    /// assert!(pages["build/index.md"].starts_with("[app](../index.md) › build"));
+    /// ```
+    #[cfg(feature = "docgen")]
+    pub fn render_markdown_pages(&self, app: impl Into<String>) -> BTreeMap<String, String> {
+        let root = self.inner.meta();
+        let mut sections = Vec::new();
+        extract_sections(&root, &self.info, &mut vec![app.into()], &mut sections);
+
+        let mut res = BTreeMap::new();
+        for (ix, section) in sections.iter().enumerate() {
+            let mut buf = Doc {
+                slash: self.info.option_style == OptionStyle::Slash,
+                ..Doc::default()
+            };
+
+            if section.path.len() > 1 {
+                buf.token(Token::BlockStart(Block::Block));
+                let (last, parents) = section.path.split_last().expect("path is never empty");
+                for depth in 0..parents.len() {
+                    let path = &section.path[..=depth];
+                    let link = page_link(&section.path, path);
+                    buf.text(&format!("[{}]({}) › ", path[depth], link));
+                }
+                buf.text(last);
+                buf.token(Token::BlockEnd(Block::Block));
+            }
+
+            buf.token(Token::BlockStart(Block::Header));
+            buf.text(&section.path.join(" "));
+            buf.token(Token::BlockEnd(Block::Header));
+
+            if ix == 0 && sections.len() > 1 {
+                buf.token(Token::BlockStart(Block::Block));
+                buf.write_str("Command summary", Style::Emphasis);
+                buf.token(Token::BlockEnd(Block::Block));
+                buf.token(Token::BlockStart(Block::Block));
+                for section in &sections {
+                    buf.token(Token::BlockStart(Block::ItemBody));
+                    buf.text(&format!(
+                        "* [`{}`]({})",
+                        section.path.join(" "),
+                        page_file(&section.path)
+                    ));
+                    buf.token(Token::BlockEnd(Block::ItemBody));
+                }
+                buf.token(Token::BlockEnd(Block::Block));
+            }
+
+            buf.doc(&render_help(
+                &section.path,
+                section.info,
+                section.meta,
+                &section.help_meta(),
+                false,
+                HelpContext::from(&self.info),
+            ));
+            if ix == 0 {
+                buf.write_extra_sections(
+                    &self.info,
+                    self.info.generated_sections,
+                    &sections,
+                    &Doc::default(),
+                );
+            }
+
+            let mut links = PageLinks::default();
+            for child in &sections {
+                if child.path.len() == section.path.len() + 1
+                    && child.path.starts_with(&section.path)
+                {
+                    let name = child.path.last().expect("path is never empty").clone();
+                    links
+                        .commands
+                        .insert(name, page_link(&section.path, &child.path));
+                }
+            }
+            let page = buf.render_markdown_with(true, Some(&links));
+            res.insert(page_file(&section.path), page.trim_start().to_owned());
+        }
+        res
+    }
+}
+
+/// File with a page for a command path, relative to the root page
+#[cfg(feature = "docgen")]
+fn page_file(path: &[String]) -> String {
+    let mut res = String::new();
+    for name in &path[1..] {
+        for c in name.chars() {
+            if c.is_alphanumeric() || c == '-' {
+                res.push(c);
+            } else {
+                let mut bytes = [0; 4];
+                for b in c.encode_utf8(&mut bytes).bytes() {
+                    res.push_str(&format!("_{:02X}", b));
+                }
+            }
+        }
+        res.push('/');
+    }
+    res.push_str("index.md");
+    res
+}
+
+/// Relative link from a page for one command path to a page for another
+#[cfg(feature = "docgen")]
+fn page_link(from: &[String], to: &[String]) -> String {
+    let common = from.iter().zip(to).take_while(|(a, b)| a == b).count();
+    let mut res = "../".repeat(from.len() - common);
+    res.push_str(&page_file(&to[common - 1..]));
+    res
+}
+
+/// Links between pages for [`render_markdown_pages`](OptionParser::render_markdown_pages)
+#[derive(Default)]
+pub(crate) struct PageLinks {
+    /// Subcommand names mentioned on the page along with files that document them
+    commands: BTreeMap<String, String>,
+}
+
+/// Html anchor for a text: lowercase alphanumeric words separated with dashes
+fn anchor(text: &str) -> String {
+    let mut res = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            res.extend(c.to_lowercase());
+        } else if !(res.is_empty() || res.ends_with('-')) {
+            res.push('-');
+        }
+    }
+    res.truncate(res.trim_end_matches('-').len());
+    res
 }
 
 #[derive(Copy, Clone, Default)]
@@ -152,6 +314,26 @@ fn change_to_markdown_style(res: &mut String, cur: &mut Styles, new: Styles) {
     *cur = new;
 }
 
+const PADDING: &str = "          ";
+
+/// Markdown list item marker: `- ` or `1. `
+fn list_marker(list: Block, count: usize) -> String {
+    if list == Block::NumberedList {
+        format!("{}. ", count)
+    } else {
+        String::from("- ")
+    }
+}
+
+/// Escape text that goes into `<pre>` blocks and attributes
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 /// Make it so new text is separated by an empty line
 fn blank_html_line(res: &mut String) {
     if !(res.is_empty() || res.ends_with("<br>\n")) {
@@ -204,7 +386,11 @@ impl Doc {
         // if we are rendering definition list or item list
         let mut stack = Vec::new();
 
-        for token in self.tokens.iter().copied() {
+        // url collected from `LinkUrl` and number of rows in the current table
+        let mut url: Option<String> = None;
+        let mut table_row = 0;
+
+        for (ix, token) in self.tokens.iter().copied().enumerate() {
             match token {
                 Token::Text { bytes, style } => {
                     let input = &self.payload[byte_pos..byte_pos + bytes];
@@ -214,8 +400,22 @@ impl Doc {
                         continue;
                     }
 
+                    if let Some(url) = &mut url {
+                        url.push_str(input);
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeLang) {
+                        continue;
+                    }
+
                     change_style(&mut res, &mut cur_style, Styles::from(style));
 
+                    if stack.last() == Some(&Block::CodeBlock) {
+                        res.push_str(&escape_html(input));
+                        continue;
+                    }
+
                     for chunk in split(input) {
                         match chunk {
                             Chunk::Raw(input, _) => {
@@ -244,14 +444,32 @@ impl Doc {
                         Block::Section2 => {
                             res.push_str("<div>\n");
                         }
-                        Block::ItemTerm => res.push_str("<dt>"),
-                        Block::ItemBody => {
-                            if stack.last().copied() == Some(Block::DefinitionList) {
-                                res.push_str("<dd>");
-                            } else {
-                                res.push_str("<li>");
-                            }
+                        Block::ItemTerm => match stack.last() {
+                            Some(Block::Table) if table_row == 0 => res.push_str("<tr><th>"),
+                            Some(Block::Table) => res.push_str("<tr><td>"),
+                            _ => res.push_str("<dt>"),
+                        },
+                        Block::ItemBody => match stack.last() {
+                            Some(Block::DefinitionList) => res.push_str("<dd>"),
+                            Some(Block::Table) if table_row == 0 => res.push_str("<th>"),
+                            Some(Block::Table) => res.push_str("<td>"),
+                            _ => res.push_str("<li>"),
+                        },
+                        Block::UnnumberedList => res.push_str("<ul>\n"),
+                        Block::NumberedList => res.push_str("<ol>\n"),
+                        Block::CodeBlock => match self.code_lang(ix, byte_pos) {
+                            Some(lang) => res.push_str(&format!(
+                                "<pre class=\"language-{}\">",
+                                escape_html(&lang)
+                            )),
+                            None => res.push_str("<pre>"),
+                        },
+                        Block::Table => {
+                            table_row = 0;
+                            res.push_str("<table>\n");
                         }
+                        Block::Link => {}
+                        Block::LinkUrl => url = Some(String::new()),
                         Block::DefinitionList => {
                             res.push_str("<dl>");
                         }
@@ -260,7 +478,7 @@ impl Doc {
                         }
                         Block::Meta => todo!(),
                         Block::Section3 => res.push_str("<div style='padding-left: 0.5em'>"),
-                        Block::Mono | Block::TermRef => {}
+                        Block::Mono | Block::TermRef | Block::CodeLang => {}
                         Block::InlineBlock => {
                             skip.push();
                         }
@@ -281,19 +499,37 @@ impl Doc {
                         Block::InlineBlock => {
                             skip.pop();
                         }
-                        Block::ItemTerm => res.push_str("</dt>\n"),
-                        Block::ItemBody => {
-                            if stack.last().copied() == Some(Block::DefinitionList) {
-                                res.push_str("</dd>\n");
-                            } else {
-                                res.push_str("</li>\n");
+                        Block::ItemTerm => match stack.last() {
+                            Some(Block::Table) if table_row == 0 => res.push_str("</th>"),
+                            Some(Block::Table) => res.push_str("</td>"),
+                            _ => res.push_str("</dt>\n"),
+                        },
+                        Block::ItemBody => match stack.last() {
+                            Some(Block::DefinitionList) => res.push_str("</dd>\n"),
+                            Some(Block::Table) => {
+                                res.push_str(if table_row == 0 {
+                                    "</th></tr>\n"
+                                } else {
+                                    "</td></tr>\n"
+                                });
+                                table_row += 1;
                             }
+                            _ => res.push_str("</li>\n"),
+                        },
+                        Block::UnnumberedList => res.push_str("</ul>\n"),
+                        Block::NumberedList => res.push_str("</ol>\n"),
+                        Block::CodeBlock => res.push_str("</pre>\n"),
+                        Block::Table => res.push_str("</table>\n"),
+                        Block::Link => res.push_str("</a>"),
+                        Block::LinkUrl => {
+                            let url = url.take().unwrap_or_default();
+                            res.push_str(&format!("<a href=\"{}\">", escape_html(&url)));
                         }
                         Block::DefinitionList => res.push_str("</dl>\n"),
                         Block::Block => {
                             res.push_str("</p>");
                         }
-                        Block::Mono | Block::TermRef => {}
+                        Block::Mono | Block::TermRef | Block::CodeLang => {}
                         Block::Section3 => res.push_str("</div>"),
                         Block::Meta => todo!(),
                     }
@@ -307,9 +543,63 @@ impl Doc {
         res
     }
 
+    /// Plain text of a block that starts at `ix`th token, `byte_pos` points to its payload
+    fn block_text(&self, ix: usize, mut byte_pos: usize) -> String {
+        let mut res = String::new();
+        let mut depth = 0;
+        for token in &self.tokens[ix + 1..] {
+            match *token {
+                Token::Text { bytes, .. } => {
+                    res.push_str(&self.payload[byte_pos..byte_pos + bytes]);
+                    byte_pos += bytes;
+                }
+                Token::BlockStart(_) => depth += 1,
+                Token::BlockEnd(_) if depth == 0 => break,
+                Token::BlockEnd(_) => depth -= 1,
+            }
+        }
+        res
+    }
+
+    /// Language of a code block that starts at `ix`th token, if it has one
+    fn code_lang(&self, ix: usize, byte_pos: usize) -> Option<String> {
+        match self.tokens.get(ix + 1) {
+            Some(Token::BlockStart(Block::CodeLang)) => Some(self.block_text(ix + 1, byte_pos)),
+            _ => None,
+        }
+    }
+
     /// Render doc into markdown document, used by documentation sample generator
     #[must_use]
     pub fn render_markdown(&self, full: bool) -> String {
+        self.render_markdown_with(full, None)
+    }
+
+    /// Render doc into markdown, with `links` terms get anchors, term references link to them
+    /// and subcommand names link to their pages
+    pub(crate) fn render_markdown_with(&self, full: bool, links: Option<&PageLinks>) -> String {
+        // anchor ids by the term token index and item names mapped to anchor ids
+        let mut term_ids = BTreeMap::new();
+        let mut anchors = BTreeMap::new();
+        if links.is_some() {
+            let mut used = BTreeSet::new();
+            for (ix, item) in &self.anchors {
+                let base = anchor(&format!("{} {}", item.kind, item.name));
+                let mut id = base.clone();
+                let mut n = 1;
+                while !used.insert(id.clone()) {
+                    n += 1;
+                    id = format!("{}-{}", base, n);
+                }
+                for name in &item.names {
+                    anchors.entry(name.clone()).or_insert_with(|| id.clone());
+                }
+                term_ids.insert(*ix, (id, item));
+            }
+        }
+        // pending closing part of a link
+        let mut link_end = None;
+
         let mut res = String::new();
         let mut byte_pos = 0;
         let mut cur_style = Styles::default();
@@ -320,6 +610,13 @@ impl Doc {
         let mut def_list = false;
         let mut code_block = false;
         let mut app_name_seen = false;
+
+        let mut stack = Vec::new();
+        // enclosing lists along with the number of items so far
+        let mut lists: Vec<(Block, usize)> = Vec::new();
+        let mut table_row = 0;
+        // url collected from `LinkUrl`
+        let mut url: Option<String> = None;
         for (ix, token) in self.tokens.iter().copied().enumerate() {
             match token {
                 Token::Text { bytes, style } => {
@@ -329,6 +626,27 @@ impl Doc {
                         continue;
                     }
 
+                    if let Some(url) = &mut url {
+                        url.push_str(input);
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeLang) {
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeBlock) {
+                        // code inside of a definition list is indented to stay in the list item
+                        for line in input.lines() {
+                            if def_list && !line.is_empty() {
+                                res.push_str("  ");
+                            }
+                            res.push_str(line);
+                            res.push('\n');
+                        }
+                        continue;
+                    }
+
                     change_to_markdown_style(&mut res, &mut cur_style, Styles::from(style));
 
                     for chunk in split(input) {
@@ -382,7 +700,46 @@ impl Doc {
                 }
                 Token::BlockStart(b) => {
                     change_to_markdown_style(&mut res, &mut cur_style, Styles::default());
+                    let parent = stack.last().copied();
+                    stack.push(b);
                     match b {
+                        Block::ItemTerm if parent == Some(Block::Table) => {
+                            new_markdown_line(&mut res);
+                            res.push_str("| ");
+                        }
+                        Block::ItemBody if parent == Some(Block::Table) => {}
+                        Block::ItemBody
+                            if !lists.is_empty() && parent == lists.last().map(|l| l.0) =>
+                        {
+                            new_markdown_line(&mut res);
+                            if let Some((_, count)) = lists.last_mut() {
+                                *count += 1;
+                            }
+                            let (current, nested) = lists.split_last().expect("checked above");
+                            for (list, count) in nested {
+                                res.push_str(&PADDING[..list_marker(*list, *count).len()]);
+                            }
+                            res.push_str(&list_marker(current.0, current.1));
+                        }
+                        Block::UnnumberedList | Block::NumberedList => {
+                            new_markdown_line(&mut res);
+                            lists.push((b, 0));
+                        }
+                        Block::Table => {
+                            blank_markdown_line(&mut res);
+                            table_row = 0;
+                        }
+                        Block::CodeBlock => {
+                            res.truncate(res.trim_end_matches(' ').len());
+                            blank_markdown_line(&mut res);
+                            if def_list {
+                                res.push_str("  ");
+                            }
+                            let lang = self.code_lang(ix, byte_pos);
+                            res.push_str(&format!("```{}\n", lang.as_deref().unwrap_or("text")));
+                        }
+                        Block::Link => {}
+                        Block::LinkUrl => url = Some(String::new()),
                         Block::Header => {
                             blank_markdown_line(&mut res);
                             if app_name_seen {
@@ -402,6 +759,14 @@ impl Doc {
                                 Some(Token::BlockEnd(Block::ItemTerm))
                             );
                             res.push_str(if empty_term { "  " } else { "- " });
+                            if let (Some(links), Some((id, item))) = (links, term_ids.get(&ix)) {
+                                res.push_str(&format!("<a id=\"{}\"></a>", id));
+                                let file = links.commands.get(&item.name);
+                                if let (Some(file), "cmd") = (file, item.kind) {
+                                    res.push('[');
+                                    link_end = Some(format!("]({})", file));
+                                }
+                            }
                         }
                         Block::ItemBody => {
                             if def_list {
@@ -421,8 +786,15 @@ impl Doc {
                         Block::Mono => {
                             mono += 1;
                         }
+                        Block::CodeLang => {}
                         Block::Section3 => res.push_str("### "),
-                        Block::TermRef => {}
+                        Block::TermRef => {
+                            let text = self.block_text(ix, byte_pos);
+                            if let Some(id) = anchors.get(text.trim()) {
+                                res.push('[');
+                                link_end = Some(format!("](#{})", id));
+                            }
+                        }
                         Block::InlineBlock => {
                             skip.push();
                         }
@@ -430,7 +802,35 @@ impl Doc {
                 }
                 Token::BlockEnd(b) => {
                     change_to_markdown_style(&mut res, &mut cur_style, Styles::default());
+                    stack.pop();
+                    let parent = stack.last().copied();
                     match b {
+                        Block::ItemTerm if parent == Some(Block::Table) => res.push_str(" | "),
+                        Block::ItemBody if parent == Some(Block::Table) => {
+                            res.push_str(" |\n");
+                            if table_row == 0 {
+                                res.push_str("| --- | --- |\n");
+                            }
+                            table_row += 1;
+                        }
+                        Block::ItemBody
+                            if !lists.is_empty() && parent == lists.last().map(|l| l.0) => {}
+                        Block::UnnumberedList | Block::NumberedList => {
+                            lists.pop();
+                            new_markdown_line(&mut res);
+                            if lists.is_empty() {
+                                res.push('\n');
+                            }
+                        }
+                        Block::Table => res.push('\n'),
+                        Block::CodeBlock => {
+                            new_markdown_line(&mut res);
+                            res.push_str(if def_list { "  ```\n" } else { "```\n\n" });
+                        }
+                        Block::LinkUrl => {
+                            res.push('[');
+                            link_end = Some(format!("]({})", url.take().unwrap_or_default()));
+                        }
                         Block::Header | Block::Block | Block::Section3 | Block::Section2 => {
                             res.push('\n');
                         }
@@ -438,7 +838,11 @@ impl Doc {
                         Block::InlineBlock => {
                             skip.pop();
                         }
-                        Block::ItemTerm | Block::TermRef => {}
+                        Block::ItemTerm | Block::TermRef | Block::Link => {
+                            if let Some(end) = link_end.take() {
+                                res.push_str(&end);
+                            }
+                        }
                         Block::ItemBody => {
                             if def_list {
                                 res.push('\n');
@@ -451,6 +855,7 @@ impl Doc {
                         Block::Mono => {
                             mono -= 1;
                         }
+                        Block::CodeLang => {}
                         Block::Meta => todo!(),
                     }
                 }
@@ -476,4 +881,84 @@ mod tests {
 
         assert_eq!(r, "<b>Usage: </b><tt><b>my_program</b></tt>")
     }
+
+    #[test]
+    fn rich_blocks() {
+        let mut doc = Doc::default();
+        doc.text("Steps:");
+        let mut deploy = Doc::default();
+        deploy.text("Deploy");
+        deploy.bullet_list(["staging", "production"]);
+        doc.numbered_list([Doc::from("Build"), deploy]);
+        doc.code_block("make <all>");
+        doc.table([("Level", "Meaning"), ("0", "quiet")]);
+        doc.link("manual", "https://example.com");
+
+        assert_eq!(
+            doc.render_markdown(true),
+            "Steps:
+1. Build
+2. Deploy
+   - staging
+   - production
+
+```text
+make <all>
+```
+
+| Level | Meaning |
+| --- | --- |
+| 0 | quiet |
+
+[manual](https://example.com)"
+        );
+
+        assert_eq!(
+            doc.render_html(true, false),
+            "Steps:<ol>
+<li>Build</li>
+<li>Deploy<ul>
+<li>staging</li>
+<li>production</li>
+</ul>
+</li>
+</ol>
+<pre>make &lt;all&gt;</pre>
+<table>
+<tr><th>Level</th><th>Meaning</th></tr>
+<tr><td>0</td><td>quiet</td></tr>
+</table>
+<a href=\"https://example.com\">manual</a>"
+        );
+    }
+
+    #[test]
+    fn term_refs_link_to_anchors() {
+        let mut doc = Doc::default();
+        doc.token(Token::BlockStart(Block::DefinitionList));
+        doc.anchor("flag", "verbose", vec!["-v".into(), "--verbose".into()]);
+        doc.token(Token::BlockStart(Block::ItemTerm));
+        doc.literal("-v, --verbose");
+        doc.token(Token::BlockEnd(Block::ItemTerm));
+        doc.token(Token::BlockStart(Block::ItemBody));
+        doc.text("Verbose output, see also ");
+        doc.token(Token::BlockStart(Block::TermRef));
+        doc.literal("-v");
+        doc.token(Token::BlockEnd(Block::TermRef));
+        doc.token(Token::BlockEnd(Block::ItemBody));
+        doc.token(Token::BlockEnd(Block::DefinitionList));
+
+        let r = doc.render_markdown_with(true, Some(&PageLinks::default()));
+        assert_eq!(
+            r,
+            "- <a id=\"flag-verbose\"></a>**`-v, --verbose`** &mdash; \n  Verbose output, see also [**`-v`**](#flag-verbose)\n\n"
+        );
+
+        // plain markdown doesn't get any links
+        let r = doc.render_markdown(true);
+        assert_eq!(
+            r,
+            "- **`-v, --verbose`** &mdash; \n  Verbose output, see also **`-v`**\n\n"
+        );
+    }
 }
@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+
 use crate::{
-    buffer::{extract_sections, manpage::escape::Apostrophes, Block, HelpItems, Style, Token},
-    Doc, OptionParser, Parser,
+    buffer::{
+        extract_sections, manpage::escape::Apostrophes, Block, DocSection, HelpItems, Style, Token,
+    },
+    info::Info,
+    Doc, OptionParser, OptionStyle, Parser,
 };
 
 mod escape;
@@ -63,7 +68,10 @@ impl<T> OptionParser<T> {
 
         extract_sections(&root, &self.info, &mut path, &mut sections);
 
-        let mut buf = Doc::default();
+        let mut buf = Doc {
+            slash: self.info.option_style == OptionStyle::Slash,
+            ..Doc::default()
+        };
 
         if sections.len() > 1 {
             buf.token(Token::BlockStart(Block::Block));
@@ -79,7 +87,7 @@ impl<T> OptionParser<T> {
                     buf.text(" ");
                 }
 
-                buf.write_meta(section.meta, true);
+                buf.write_usage(section.meta, self.info.layout.usage);
                 buf.text("\n");
             }
             buf.token(Token::BlockEnd(Block::Meta));
@@ -114,7 +122,7 @@ impl<T> OptionParser<T> {
             buf.text("SYNOPSIS");
             buf.token(Token::BlockEnd(Block::Header));
             buf.write_path(&section.path);
-            buf.write_meta(section.meta, true);
+            buf.write_usage(section.meta, self.info.layout.usage);
 
             if let Some(t) = &section.info.header {
                 buf.token(Token::BlockStart(Block::Block));
@@ -124,9 +132,9 @@ impl<T> OptionParser<T> {
 
             let mut items = HelpItems::default();
             items.append_meta(section.meta);
-            let help_meta = section.info.meta();
+            let help_meta = section.help_meta();
             items.append_meta(&help_meta);
-            buf.write_help_item_groups(items, false);
+            buf.write_help_item_groups(items, false, &self.info.layout);
 
             if let Some(footer) = &section.info.footer {
                 buf.token(Token::BlockStart(Block::Block));
@@ -135,6 +143,13 @@ impl<T> OptionParser<T> {
             }
         }
 
+        buf.write_extra_sections(
+            &self.info,
+            self.info.generated_sections,
+            &sections,
+            &Doc::default(),
+        );
+
         let mut manpage = Roff::new();
         manpage.control(
             "TH",
@@ -151,6 +166,141 @@ impl<T> OptionParser<T> {
 
         buf.render_roff(manpage)
     }
+
+    /// Render command line documentation into a separate manpage for the app and every subcommand
+    ///
+    /// Pages are named `git` style: `app`, `app-build`, `app-build-deploy` and refer to each
+    /// other in the SEE ALSO section. Returns a map from a file name such as `app-build.1` to
+    /// the page contents, the rest of the parameters are the same as for
+    /// [`render_manpage`](OptionParser::render_manpage).
+    ///
+    /// ```rust
+    /// # use bpaf::{*, doc::Section};
+    /// let build = short('r').help("Build in release mode").switch()
+    ///     .to_options()
+    ///     .descr("Build the project")
+    ///     .command("build");
+    /// let pages = build.to_options()
+    ///     .descr("Project manager")
+    ///     .render_manpages("app", Section::General, None, None, None);
+    /// let names = pages.keys().map(String::as_str).collect::<Vec<_>>();
+    /// assert_eq!(names, ["app-build.1", "app.1"]);
+    /// ```
+    pub fn render_manpages(
+        &self,
+        app: impl AsRef<str>,
+        section: Section,
+        last_update_date: Option<&str>,
+        vendor: Option<&str>,
+        application_title: Option<&str>,
+    ) -> BTreeMap<String, String> {
+        let mut sections = Vec::new();
+        let root = self.inner.meta();
+        let mut path = vec![app.as_ref().to_string()];
+
+        extract_sections(&root, &self.info, &mut path, &mut sections);
+
+        let mut res = BTreeMap::new();
+        for page in &sections {
+            let name = page.path.join("-");
+            let mut buf = Doc {
+                slash: self.info.option_style == OptionStyle::Slash,
+                ..Doc::default()
+            };
+            write_page(&mut buf, &name, page, &sections, section, &self.info);
+
+            let mut manpage = Roff::new();
+            manpage.control(
+                "TH",
+                [
+                    name.as_str(),
+                    section.as_str(),
+                    last_update_date.unwrap_or("-"),
+                    vendor.unwrap_or("-"),
+                    application_title.unwrap_or(""),
+                ]
+                .iter()
+                .copied(),
+            );
+            res.insert(
+                format!("{}.{}", name, section.as_str()),
+                buf.render_roff(manpage),
+            );
+        }
+        res
+    }
+}
+
+/// Contents of a single page for [`render_manpages`](OptionParser::render_manpages)
+fn write_page(
+    buf: &mut Doc,
+    name: &str,
+    page: &DocSection,
+    sections: &[DocSection],
+    section: Section,
+    root: &Info,
+) {
+    let layout = &root.layout;
+    if let Some(descr) = page.info.descr.as_ref().and_then(Doc::first_line) {
+        buf.token(Token::BlockStart(Block::Header));
+        buf.text("NAME");
+        buf.token(Token::BlockEnd(Block::Header));
+        buf.text(name);
+        buf.text(" - ");
+        buf.doc(&descr);
+    }
+
+    buf.token(Token::BlockStart(Block::Header));
+    buf.text("SYNOPSIS");
+    buf.token(Token::BlockEnd(Block::Header));
+    buf.write_path(&page.path);
+    buf.write_usage(page.meta, layout.usage);
+
+    buf.token(Token::BlockStart(Block::Header));
+    buf.text("DESCRIPTION");
+    buf.token(Token::BlockEnd(Block::Header));
+    for doc in [&page.info.descr, &page.info.header]
+        .iter()
+        .copied()
+        .flatten()
+    {
+        buf.token(Token::BlockStart(Block::Block));
+        buf.doc(doc);
+        buf.token(Token::BlockEnd(Block::Block));
+    }
+
+    let mut items = HelpItems::default();
+    items.append_meta(page.meta);
+    let help_meta = page.help_meta();
+    items.append_meta(&help_meta);
+    buf.write_help_item_groups(items, false, layout);
+
+    if let Some(footer) = &page.info.footer {
+        buf.token(Token::BlockStart(Block::Block));
+        buf.doc(footer);
+        buf.token(Token::BlockEnd(Block::Block));
+    }
+
+    // parent page followed by pages for immediate subcommands
+    let depth = page.path.len();
+    let related = sections.iter().filter(|other| {
+        (other.path.len() + 1 == depth && page.path.starts_with(&other.path))
+            || (other.path.len() == depth + 1 && other.path.starts_with(&page.path))
+    });
+    let mut see_also = Doc::default();
+    for (ix, other) in related.enumerate() {
+        if ix > 0 {
+            see_also.text(", ");
+        }
+        see_also.literal(&other.path.join("-"));
+        see_also.text(&format!("({})", section.as_str()));
+    }
+    buf.write_extra_sections(
+        page.info,
+        root.generated_sections,
+        std::slice::from_ref(page),
+        &see_also,
+    );
 }
 
 impl From<Style> for Font {
@@ -170,6 +320,12 @@ impl Doc {
         // We also strip styling from them and change sections to all caps
         let mut capture = (String::new(), false);
 
+        let mut stack = Vec::new();
+        // item counters for enclosing lists, `None` for bulleted ones
+        let mut lists: Vec<Option<usize>> = Vec::new();
+        // url collected from `LinkUrl`
+        let mut url: Option<String> = None;
+
         let mut byte_pos = 0;
         for token in self.tokens.iter().copied() {
             match token {
@@ -177,6 +333,20 @@ impl Doc {
                     let input = &self.payload[byte_pos..byte_pos + bytes];
                     byte_pos += bytes;
 
+                    if let Some(url) = &mut url {
+                        url.push_str(input);
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeLang) {
+                        continue;
+                    }
+
+                    if stack.last() == Some(&Block::CodeBlock) {
+                        roff.text(&[(Font::Roman, input)]).roff_linebreak();
+                        continue;
+                    }
+
                     if capture.1 {
                         capture.0.push_str(input);
                         continue;
@@ -188,8 +358,41 @@ impl Doc {
                     }
                 }
                 Token::BlockStart(block) => {
-                    //
+                    let parent = stack.last().copied();
+                    stack.push(block);
                     match block {
+                        Block::ItemBody
+                            if matches!(
+                                parent,
+                                Some(Block::UnnumberedList | Block::NumberedList)
+                            ) =>
+                        {
+                            match lists.last_mut() {
+                                Some(Some(n)) => {
+                                    *n += 1;
+                                    roff.control("IP", [format!("{}.", n).as_str(), "4"]);
+                                }
+                                _ => {
+                                    roff.control("IP", ["\\(bu", "2"]);
+                                }
+                            }
+                        }
+                        Block::UnnumberedList | Block::NumberedList => {
+                            // nested lists are indented relative to the parent item
+                            if !lists.is_empty() {
+                                roff.control0("RS");
+                            }
+                            lists.push(if block == Block::NumberedList {
+                                Some(0)
+                            } else {
+                                None
+                            });
+                        }
+                        Block::CodeBlock => {
+                            roff.control0("PP").control0("EX").strip_newlines(false);
+                        }
+                        Block::Table | Block::Link | Block::CodeLang => {}
+                        Block::LinkUrl => url = Some(String::new()),
                         Block::Header | Block::Section2 | Block::Section3 => {
                             capture.1 = true;
                         }
@@ -211,8 +414,28 @@ impl Doc {
                     }
                 }
                 Token::BlockEnd(block) => {
-                    //
+                    stack.pop();
+                    let parent = stack.last().copied();
                     match block {
+                        Block::ItemBody
+                            if matches!(
+                                parent,
+                                Some(Block::UnnumberedList | Block::NumberedList)
+                            ) => {}
+                        Block::UnnumberedList | Block::NumberedList => {
+                            lists.pop();
+                            roff.control0(if lists.is_empty() { "PP" } else { "RE" });
+                        }
+                        Block::CodeBlock => {
+                            roff.control0("EE");
+                        }
+                        Block::Table | Block::CodeLang => {}
+                        Block::LinkUrl => {
+                            roff.control("UR", [url.take().unwrap_or_default()]);
+                        }
+                        Block::Link => {
+                            roff.control0("UE");
+                        }
                         Block::Header => {
                             capture.1 = false;
                             roff.control("SH", [capture.0.to_uppercase()]);
@@ -0,0 +1,278 @@
+use std::path::Path;
+
+use crate::{
+    buffer::{html::collect_html, Block, Style, Token},
+    info::{Info, OptionStyle},
+    item::Item,
+    meta_help::{render_help, HelpContext},
+    Doc, Meta, OptionParser,
+};
+
+const REGION_START: &str = "<!-- bpaf:";
+const REGION_END: &str = "<!-- bpaf:end -->";
+
+/// A marked region that doesn't match freshly rendered documentation
+struct Stale {
+    marker: String,
+    old: String,
+    new: String,
+}
+
+impl<T> OptionParser<T> {
+    /// Rewrite marked regions in a markdown file with freshly rendered documentation
+    ///
+    /// A region starts with a marker `<!-- bpaf:KIND APP [COMMAND...] -->` on a separate line
+    /// and ends with `<!-- bpaf:end -->`, everything in between gets replaced. `APP` is the
+    /// application name to use, optional `COMMAND` names select a subcommand. Supported kinds
+    /// are:
+    /// - `help` - `--help` output in a `text` code block
+    /// - `usage` - usage line in a `text` code block
+    /// - `markdown` - the same output as [`render_markdown`](OptionParser::render_markdown)
+    ///
+    /// Markers inside of fenced code blocks are left alone so files can show examples of regions.
+    /// Generated content uses the same line endings as the first line of the file, `\n` or
+    /// `\r\n`.
+    ///
+    /// Returns `true` if file was changed. Use [`check_doc_regions`](OptionParser::check_doc_regions)
+    /// in CI to make sure files stay up to date.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if file can't be read or written or contains invalid
+    /// regions
+    pub fn update_doc_regions(&self, file: impl AsRef<Path>) -> Result<bool, String> {
+        let file = file.as_ref();
+        let input =
+            std::fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        let (output, stale) = self
+            .render_regions(&input)
+            .map_err(|e| format!("{}: {}", file.display(), e))?;
+        if stale.is_empty() {
+            Ok(false)
+        } else {
+            std::fs::write(file, output).map_err(|e| format!("{}: {}", file.display(), e))?;
+            Ok(true)
+        }
+    }
+
+    /// Check that marked regions in a markdown file match freshly rendered documentation
+    ///
+    /// See [`update_doc_regions`](OptionParser::update_doc_regions) for region syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns a diff style description of all the regions that are out of date along with
+    /// file access or syntax errors
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// # let dir = std::env::temp_dir().join("bpaf_check_doc_regions");
+    /// # std::fs::create_dir_all(&dir).unwrap();
+    /// # let readme = dir.join("README.md");
+    /// # std::fs::write(&readme, "<!-- bpaf:usage app -->\n<!-- bpaf:end -->\n").unwrap();
+    /// let parser = short('v').switch().to_options();
+    /// assert!(parser.check_doc_regions(&readme).is_err());
+    /// parser.update_doc_regions(&readme).unwrap();
+    /// assert!(parser.check_doc_regions(&readme).is_ok());
+    /// ```
+    pub fn check_doc_regions(&self, file: impl AsRef<Path>) -> Result<(), String> {
+        let file = file.as_ref();
+        let input =
+            std::fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        let (_, stale) = self
+            .render_regions(&input)
+            .map_err(|e| format!("{}: {}", file.display(), e))?;
+        if stale.is_empty() {
+            return Ok(());
+        }
+        let mut res = String::new();
+        for Stale { marker, old, new } in stale {
+            res.push_str(&format!(
+                "{}: region `{}` is out of date\n",
+                file.display(),
+                marker
+            ));
+            write_diff(&mut res, &old, &new);
+        }
+        Err(res)
+    }
+
+    /// Replace contents of all the marked regions, returns updated text and regions that changed
+    fn render_regions(&self, input: &str) -> Result<(String, Vec<Stale>), String> {
+        let mut output = String::new();
+        let mut stale = Vec::new();
+        let crlf = input
+            .find('\n')
+            .map_or(false, |ix| input[..ix].ends_with('\r'));
+        let mut lines = input.split_inclusive('\n');
+        // opening fence of a code block we are currently in
+        let mut code_block = None;
+        while let Some(line) = lines.next() {
+            output.push_str(line);
+            if let Some(open) = code_block {
+                if fence(line).map_or(false, |close| close.starts_with(open)) {
+                    code_block = None;
+                }
+                continue;
+            }
+            if let Some(open) = fence(line) {
+                code_block = Some(open);
+                continue;
+            }
+            let marker = match line
+                .trim()
+                .strip_prefix(REGION_START)
+                .and_then(|m| m.strip_suffix("-->"))
+            {
+                Some(marker) => marker.trim(),
+                None => continue,
+            };
+            if marker == "end" {
+                return Err(String::from(
+                    "`<!-- bpaf:end -->` without a matching region",
+                ));
+            }
+
+            let mut old = String::new();
+            let end = loop {
+                match lines.next() {
+                    Some(line) if line.trim() == REGION_END => break line,
+                    Some(line) => old.push_str(line),
+                    None => return Err(format!("region `{}` is not closed", marker)),
+                }
+            };
+            let mut new = self.render_region(marker)?;
+            if crlf {
+                new = new.replace('\n', "\r\n");
+            }
+            output.push_str(&new);
+            output.push_str(end);
+            if old != new {
+                stale.push(Stale {
+                    marker: marker.to_owned(),
+                    old,
+                    new,
+                });
+            }
+        }
+        Ok((output, stale))
+    }
+
+    /// Render contents of a single region
+    fn render_region(&self, marker: &str) -> Result<String, String> {
+        let mut words = marker.split_whitespace();
+        let kind = words.next().unwrap_or("");
+        let app = words
+            .next()
+            .ok_or_else(|| format!("region `{}` needs an application name", marker))?;
+
+        let root = self.inner.meta();
+        let mut meta = &root;
+        let mut info = &self.info;
+        let mut path = vec![app.to_owned()];
+        let mut globals = Vec::new();
+        let mut commands = Vec::new();
+        for word in words {
+            commands.clear();
+            meta.collect_commands(&mut commands);
+            match find_command(&commands, word) {
+                Some((inner_meta, inner_info)) => {
+                    meta.collect_globals(&mut globals);
+                    meta = inner_meta;
+                    info = inner_info;
+                    path.push(word.to_owned());
+                }
+                None => return Err(format!("region `{}`: no such command `{}`", marker, word)),
+            }
+        }
+
+        match kind {
+            "help" => {
+                let help = render_help(
+                    &path,
+                    info,
+                    meta,
+                    &info.help_meta(&globals),
+                    true,
+                    HelpContext::from(&self.info),
+                );
+                Ok(format!("```text\n{}```\n", help.monochrome(false)))
+            }
+            "usage" => {
+                let mut buf = Doc {
+                    slash: self.info.option_style == OptionStyle::Slash,
+                    ..Doc::default()
+                };
+                if let Some(usage) = &info.usage {
+                    buf.doc(usage);
+                } else {
+                    buf.write_str("Usage", Style::Emphasis);
+                    buf.write_str(": ", Style::Text);
+                    buf.token(Token::BlockStart(Block::Mono));
+                    buf.write_path(&path);
+                    buf.write_usage(meta, self.info.layout.usage);
+                    buf.token(Token::BlockEnd(Block::Mono));
+                }
+                Ok(format!(
+                    "```text\n{}\n```\n",
+                    buf.monochrome(false).trim_end()
+                ))
+            }
+            "markdown" => {
+                let md = collect_html(path.join(" "), meta, info).render_markdown(true);
+                Ok(format!("{}\n", md.trim()))
+            }
+            _ => Err(format!(
+                "region `{}`: unknown kind `{}`, expected `help`, `usage` or `markdown`",
+                marker, kind
+            )),
+        }
+    }
+}
+
+/// Code block fence at the start of the line: three or more backticks or tildes
+fn fence(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let c = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = line.len() - line.trim_start_matches(c).len();
+    if len >= 3 {
+        Some(&line[..len])
+    } else {
+        None
+    }
+}
+
+fn find_command<'a>(commands: &[&'a Item], name: &str) -> Option<(&'a Meta, &'a Info)> {
+    commands.iter().find_map(|item| match item {
+        Item::Command {
+            name: n,
+            meta,
+            info,
+            ..
+        } if *n == name => Some((meta.as_ref(), info.as_ref())),
+        _ => None,
+    })
+}
+
+/// Lines that differ between `old` and `new`, common lines at the start and the end are skipped
+fn write_diff(res: &mut String, old: &str, new: &str) {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for line in &old[prefix..old.len() - suffix] {
+        res.push('-');
+        res.push_str(line);
+        res.push('\n');
+    }
+    for line in &new[prefix..new.len() - suffix] {
+        res.push('+');
+        res.push_str(line);
+        res.push('\n');
+    }
+}
@@ -1,3 +1,5 @@
+use super::width::{char_width, is_cjk, str_width};
+
 pub(super) struct Splitter<'a> {
     input: &'a str,
 
@@ -14,7 +16,7 @@ enum Code {
 
 /// Split payload into chunks annotated with character width and containing no newlines according
 /// to text formatting rules
-pub(super) fn split(input: &str) -> Splitter {
+pub(super) fn split(input: &str) -> Splitter<'_> {
     Splitter {
         input,
         #[cfg(feature = "docgen")]
@@ -114,21 +116,24 @@ impl<'a> Iterator for Splitter<'a> {
             self.input = tail;
             Some(Chunk::Raw(" ", 1))
         } else {
-            let mut char_ix = 0;
-
-            // there's iterator position but it won't give me character length of the rest of the input
-            for (byte_ix, chr) in self.input.char_indices() {
-                if chr == '\n' || chr == ' ' {
-                    let head = &self.input[..byte_ix];
-                    let tail = &self.input[byte_ix..];
-                    self.input = tail;
-                    return Some(Chunk::Raw(head, char_ix));
+            let mut end = self.input.len();
+            let mut prev_cjk = false;
+            for (ix, c) in self.input.char_indices() {
+                if c == '\n' || c == ' ' {
+                    end = ix;
+                    break;
+                }
+                // CJK text has no spaces, it can be wrapped before or after every character
+                let cjk = is_cjk(c);
+                if ix > 0 && (cjk || prev_cjk) && char_width(c) > 0 {
+                    end = ix;
+                    break;
                 }
-                char_ix += 1;
+                prev_cjk |= cjk;
             }
-            let head = self.input;
-            self.input = "";
-            Some(Chunk::Raw(head, char_ix))
+            let (head, tail) = self.input.split_at(end);
+            self.input = tail;
+            Some(Chunk::Raw(head, str_width(head)))
         }
     }
 }
@@ -178,3 +183,21 @@ fn ticks_code_block() {
         ],
     );
 }
+
+#[test]
+fn wide_chars() {
+    use Chunk::*;
+    let xs = split("日本語 text 🦀crab").collect::<Vec<_>>();
+    assert_eq!(
+        xs,
+        [
+            Raw("日", 2),
+            Raw("本", 2),
+            Raw("語", 2),
+            Raw(" ", 1),
+            Raw("text", 4),
+            Raw(" ", 1),
+            Raw("🦀crab", 6),
+        ]
+    );
+}
@@ -0,0 +1,170 @@
+//! Display width of text in a terminal
+//!
+//! Wide (East Asian Wide and Fullwidth, emoji) characters take two columns, combining marks,
+//! zero width and control characters take none, characters joined with ZWJ are rendered as a
+//! single glyph with the width of the first one.
+
+/// Characters that take two columns, sorted
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18AFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F3FA),
+    (0x1F400, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x3FFFD),
+];
+
+/// Characters that don't take any space on their own, sorted
+const ZERO: &[(u32, u32)] = &[
+    (0x0000, 0x001F),
+    (0x007F, 0x009F),
+    (0x00AD, 0x00AD),
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x2028, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302F),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0000, 0xE0FFF),
+];
+
+const ZWJ: char = '\u{200D}';
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+fn in_table(table: &[(u32, u32)], c: char) -> bool {
+    let c = c as u32;
+    table
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                std::cmp::Ordering::Less
+            } else if lo > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Ideographs, kana and hangul - text that doesn't use spaces between words and can be wrapped
+/// between any two characters
+pub(super) fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x2E80..=0x9FFF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFF00..=0xFF60 | 0x20000..=0x3FFFD)
+}
+
+/// Number of terminal columns a single character takes
+pub(super) fn char_width(c: char) -> usize {
+    if c.is_ascii() {
+        usize::from(!c.is_ascii_control())
+    } else if in_table(ZERO, c) {
+        0
+    } else if in_table(WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Number of terminal columns a string takes
+pub(crate) fn str_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut prev = None;
+    for c in s.chars() {
+        width += match (prev, c) {
+            // the rest of ZWJ sequence is drawn as part of the first character
+            (Some(ZWJ), _) => 0,
+            // emoji style for a text symbol such as ❤️
+            (Some(p), EMOJI_PRESENTATION) if char_width(p) == 1 => 1,
+            _ => char_width(c),
+        };
+        prev = Some(c);
+    }
+    width
+}
+
+#[test]
+fn widths() {
+    assert_eq!(str_width("hello"), 5);
+    assert_eq!(str_width("héllo"), 5);
+    assert_eq!(str_width("he\u{301}llo"), 5);
+    assert_eq!(str_width("日本語"), 6);
+    assert_eq!(str_width("한국어"), 6);
+    assert_eq!(str_width("ｆｕｌｌ"), 8);
+    assert_eq!(str_width("🦀"), 2);
+    assert_eq!(str_width("❤\u{FE0F}"), 2);
+    assert_eq!(str_width("👍🏽"), 2);
+    assert_eq!(str_width("👩\u{200D}💻"), 2);
+    assert_eq!(str_width("🇯🇵"), 2);
+    assert!(is_cjk('語') && is_cjk('한') && !is_cjk('a') && !is_cjk('🦀'));
+}
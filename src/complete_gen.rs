@@ -19,7 +19,7 @@ use crate::{
     complete_shell::{render_bash, render_fish, render_simple, render_test, render_zsh},
     item::ShortLong,
     parsers::NamedArg,
-    Doc, ShellComp,
+    Doc, OptionStyle, ShellComp,
 };
 use std::ffi::OsStr;
 
@@ -47,18 +47,27 @@ impl Complete {
 impl State {
     /// Add a new completion hint for flag, if needed
     pub(crate) fn push_flag(&mut self, named: &NamedArg) {
+        self.push_flag_name(ShortLong::try_from(named).ok(), &named.help, named.global);
+    }
+
+    /// Add a new completion hint for a flag with a given name, if needed
+    pub(crate) fn push_flag_name(
+        &mut self,
+        name: Option<ShortLong>,
+        help: &Option<Doc>,
+        global: bool,
+    ) {
         let depth = self.depth();
-        if let Some(comp) = self.comp_mut() {
-            if let Ok(name) = ShortLong::try_from(named) {
-                comp.comps.push(Comp::Flag {
-                    extra: CompExtra {
-                        depth,
-                        group: None,
-                        help: named.help.as_ref().and_then(Doc::to_completion),
-                    },
-                    name,
-                });
-            }
+        if let (Some(comp), Some(name)) = (self.comp_mut(), name) {
+            comp.comps.push(Comp::Flag {
+                extra: CompExtra {
+                    depth,
+                    group: None,
+                    help: help.as_ref().and_then(Doc::to_completion),
+                    global,
+                },
+                name,
+            });
         }
     }
 
@@ -72,6 +81,7 @@ impl State {
                         depth,
                         group: None,
                         help: named.help.as_ref().and_then(Doc::to_completion),
+                        global: named.global,
                     },
                     metavar,
                     name,
@@ -83,12 +93,13 @@ impl State {
     /// Add a new completion hint for metadata, if needed
     ///
     /// `is_argument` is set to true when we are trying to parse the value and false if
-    /// when meta
+    /// when meta, `global` is set for values of global arguments
     pub(crate) fn push_metavar(
         &mut self,
         meta: &'static str,
         help: &Option<Doc>,
         is_argument: bool,
+        global: bool,
     ) {
         let depth = self.depth();
         if let Some(comp) = self.comp_mut() {
@@ -96,6 +107,7 @@ impl State {
                 depth,
                 group: None,
                 help: help.as_ref().and_then(Doc::to_completion),
+                global,
             };
 
             comp.comps.push(Comp::Metavariable {
@@ -120,6 +132,7 @@ impl State {
                     depth,
                     group: None,
                     help: help.as_ref().and_then(Doc::to_completion),
+                    global: false,
                 },
                 name,
                 short,
@@ -145,6 +158,7 @@ impl State {
                     depth,
                     group: None,
                     help: Some("Positional only items after this token".to_owned()),
+                    global: false,
                 },
                 body: "--".to_owned(),
                 is_argument: false,
@@ -172,6 +186,7 @@ impl Complete {
                 depth,
                 group: None,
                 help: None,
+                global: false,
             },
             script: op,
         });
@@ -188,7 +203,12 @@ impl Complete {
         self.comps.push(Comp::Value {
             body,
             is_argument,
-            extra: CompExtra { depth, group, help },
+            extra: CompExtra {
+                depth,
+                group,
+                help,
+                global: false,
+            },
         });
     }
 
@@ -200,7 +220,7 @@ impl Complete {
         self.comps.extend(comps);
     }
 
-    pub(crate) fn drain_comps(&mut self) -> std::vec::Drain<Comp> {
+    pub(crate) fn drain_comps(&mut self) -> std::vec::Drain<'_, Comp> {
         self.comps.drain(0..)
     }
 
@@ -219,6 +239,9 @@ pub(crate) struct CompExtra {
 
     /// help message attached to a completion item
     pub(crate) help: Option<String>,
+
+    /// global items are offered at every depth
+    pub(crate) global: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -236,7 +259,6 @@ pub(crate) enum Comp {
         metavar: &'static str,
     },
 
-    ///
     Command {
         extra: CompExtra,
         name: &'static str,
@@ -268,13 +290,17 @@ pub(crate) enum Comp {
 impl Comp {
     /// to avoid leaking items with higher depth into items with lower depth
     fn depth(&self) -> usize {
+        self.extra().depth
+    }
+
+    fn extra(&self) -> &CompExtra {
         match self {
             Comp::Command { extra, .. }
             | Comp::Value { extra, .. }
             | Comp::Flag { extra, .. }
             | Comp::Shell { extra, .. }
             | Comp::Metavariable { extra, .. }
-            | Comp::Argument { extra, .. } => extra.depth,
+            | Comp::Argument { extra, .. } => extra,
         }
     }
 
@@ -337,9 +363,11 @@ impl Arg {
                     Some((self, s))
                 }
             }
-            Arg::Long(_, _, s) | Arg::ArgWord(s) | Arg::Word(s) | Arg::PosWord(s) => {
-                Some((self, s))
-            }
+            Arg::Long(_, _, s)
+            | Arg::Single(_, _, s)
+            | Arg::ArgWord(s)
+            | Arg::Word(s)
+            | Arg::PosWord(s) => Some((self, s)),
         }
     }
 }
@@ -353,6 +381,8 @@ enum Prefix<'a> {
     NA,
     Short(char),
     Long(&'a str),
+    /// `/name:` or `/name=`, kept as typed
+    Slash(&'a str),
 }
 
 impl State {
@@ -374,7 +404,7 @@ impl State {
         // try get a current item to complete - must be non-virtual right most one
         // value must be present here, and can fail only for non-utf8 values
         // can't do much completing with non-utf8 values since bpaf needs to print them to stdout
-        let (_, lit) = items.next()?;
+        let (current, lit) = items.next()?;
 
         // For cases like "-k=val", "-kval", "--key=val", "--key val"
         // last value is going  to be either Arg::Word or Arg::ArgWord
@@ -386,16 +416,29 @@ impl State {
                 (false, full_lit)
             }
             Some((Arg::PosWord(_), _)) => (true, lit),
+            // past the first positional item in options first mode
+            _ if matches!(current, Arg::PosWord(_)) && lit != "--" => (true, lit),
             _ => (false, lit),
         };
 
         let prefix = match preceeding {
+            Some((Arg::Short(_, true, os) | Arg::Long(_, true, os), full_lit))
+                if os.to_str().map_or(false, |s| s.starts_with('/')) =>
+            {
+                Prefix::Slash(&full_lit[..full_lit.len() - lit.len()])
+            }
             Some((Arg::Short(s, true, _os), _lit)) => Prefix::Short(*s),
             Some((Arg::Long(l, true, _os), _lit)) => Prefix::Long(l.as_str()),
             _ => Prefix::NA,
         };
 
-        let (items, shell) = comp.complete(lit, pos_only, prefix);
+        let slash = match self.style {
+            OptionStyle::Dash => false,
+            OptionStyle::DashAndSlash => lit.starts_with('/'),
+            OptionStyle::Slash => true,
+        };
+
+        let (items, shell) = comp.complete(lit, pos_only, prefix, slash);
 
         Some(match comp.output_rev {
             0 => render_test(&items, &shell, full_lit),
@@ -412,18 +455,52 @@ impl State {
 }
 
 /// Try to expand short string names into long names if possible
-fn preferred_name(name: ShortLong) -> String {
+fn preferred_name(name: ShortLong, slash: bool) -> String {
     match name {
+        ShortLong::Short(s) if slash => format!("/{}", s),
+        ShortLong::Long(l) | ShortLong::ShortLong(_, l) if slash => format!("/{}", l),
         ShortLong::Short(s) => format!("-{}", s),
         ShortLong::Long(l) | ShortLong::ShortLong(_, l) => format!("--{}", l),
+        ShortLong::Single(l, _) => format!("-{}", l),
     }
 }
 
 // check if argument can possibly match the argument passed in and returns a preferrable replacement
-fn arg_matches(arg: &str, name: ShortLong) -> Option<String> {
-    // "" and "-" match any flag
-    if arg.is_empty() || arg == "-" {
-        return Some(preferred_name(name));
+fn arg_matches(arg: &str, name: ShortLong, slash: bool) -> Option<String> {
+    // "" and "-" match any flag, "/" does the same for slash style names
+    if arg.is_empty() || arg == if slash { "/" } else { "-" } {
+        return Some(preferred_name(name, slash));
+    }
+
+    // slash style uses the same prefix for short and long names: `/v`, `/verbose`
+    if slash {
+        let s = arg.strip_prefix('/')?;
+        return match name {
+            ShortLong::Short(c) | ShortLong::ShortLong(c, _)
+                if s.strip_prefix(c).map_or(false, str::is_empty) =>
+            {
+                Some(preferred_name(name, slash))
+            }
+            ShortLong::Long(l) | ShortLong::ShortLong(_, l) if l.starts_with(s) => {
+                Some(preferred_name(name, slash))
+            }
+            _ => None,
+        };
+    }
+
+    // single dash names and toggles are matched separately since they keep their own prefix
+    if let ShortLong::Single(l, toggle) = name {
+        if let Some(s) = arg.strip_prefix('-') {
+            if !s.starts_with('-') && l.starts_with(s) {
+                return Some(format!("-{}", l));
+            }
+        }
+        if let Some(s) = arg.strip_prefix('+') {
+            if toggle && l.starts_with(s) {
+                return Some(format!("+{}", l));
+            }
+        }
+        return None;
     }
 
     let mut can_match = false;
@@ -431,7 +508,7 @@ fn arg_matches(arg: &str, name: ShortLong) -> Option<String> {
     // separately check for short and long names, fancy strip prefix things is here to avoid
     // allocations and cloning
     match name {
-        ShortLong::Long(_) => {}
+        ShortLong::Long(_) | ShortLong::Single(_, _) => {}
         ShortLong::Short(s) | ShortLong::ShortLong(s, _) => {
             can_match |= arg
                 .strip_prefix('-')
@@ -442,14 +519,14 @@ fn arg_matches(arg: &str, name: ShortLong) -> Option<String> {
 
     // and long string too
     match name {
-        ShortLong::Short(_) => {}
+        ShortLong::Short(_) | ShortLong::Single(_, _) => {}
         ShortLong::Long(l) | ShortLong::ShortLong(_, l) => {
             can_match |= arg.strip_prefix("--").map_or(false, |s| l.starts_with(s));
         }
     }
 
     if can_match {
-        Some(preferred_name(name))
+        Some(preferred_name(name, slash))
     } else {
         None
     }
@@ -494,7 +571,8 @@ impl Complete {
         arg: &str,
         pos_only: bool,
         prefix: Prefix,
-    ) -> (Vec<ShowComp>, Vec<ShellComp>) {
+        slash: bool,
+    ) -> (Vec<ShowComp<'_>>, Vec<ShellComp>) {
         let mut items: Vec<ShowComp> = Vec::new();
         let mut shell = Vec::new();
         let max_depth = self.comps.iter().map(Comp::depth).max().unwrap_or(0);
@@ -503,7 +581,7 @@ impl Complete {
         for item in self
             .comps
             .iter()
-            .filter(|c| c.depth() == max_depth && (!pos_only || c.is_pos()))
+            .filter(|c| (c.depth() == max_depth || c.extra().global) && (!pos_only || c.is_pos()))
         {
             match (only_values, item.only_value()) {
                 (true, true) | (false, false) => {}
@@ -526,7 +604,7 @@ impl Complete {
                 }
 
                 Comp::Flag { name, extra } => {
-                    if let Some(long) = arg_matches(arg, *name) {
+                    if let Some(long) = arg_matches(arg, *name, slash) {
                         items.push(ShowComp {
                             pretty: long.clone(),
                             subst: long,
@@ -540,9 +618,13 @@ impl Complete {
                     metavar,
                     extra,
                 } => {
-                    if let Some(long) = arg_matches(arg, *name) {
+                    if let Some(long) = arg_matches(arg, *name, slash) {
                         items.push(ShowComp {
-                            pretty: format!("{}={}", long, metavar),
+                            pretty: match name {
+                                ShortLong::Single(_, _) => format!("{} {}", long, metavar),
+                                _ if slash => format!("{}:{}", long, metavar),
+                                _ => format!("{}={}", long, metavar),
+                            },
                             subst: long,
                             extra,
                         });
@@ -561,6 +643,7 @@ impl Complete {
                             Prefix::NA => body.clone(),
                             Prefix::Short(s) => format!("-{}={}", s, body),
                             Prefix::Long(l) => format!("--{}={}", l, body),
+                            Prefix::Slash(p) => format!("{}{}", p, body),
                         },
                     });
                 }
@@ -1,13 +1,13 @@
 use std::ops::Range;
 
 use crate::{
-    args::{Arg, State},
-    buffer::{Block, Color, Doc, Style, Token},
-    item::Item,
-    item::ShortLong,
+    args::{Arg, KnownNames, State},
+    buffer::{str_width, Block, Color, Doc, Style, Token},
+    info::ErrorStyle,
+    item::{Item, ShortLong},
     meta_help::Metavar,
     meta_youmean::{Suggestion, Variant},
-    Meta,
+    Meta, OptionStyle,
 };
 
 /// Unsuccessful command line parsing outcome, internal representation
@@ -20,7 +20,7 @@ impl Error {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum Message {
     // those can be caught ---------------------------------------------------------------
     /// Tried to consume an env variable with no fallback, variable was not set
@@ -29,6 +29,9 @@ pub(crate) enum Message {
     /// User specified an error message on some
     ParseSome(&'static str),
 
+    /// Bounded repetition collected fewer items than required: meta, got, min, max
+    Cardinality(Box<Meta>, usize, usize, usize),
+
     /// User asked for parser to fail explicitly
     ParseFail(&'static str),
 
@@ -43,15 +46,18 @@ pub(crate) enum Message {
     // those cannot be caught-------------------------------------------------------------
     /// Parsing failed and this is the final output
     ParseFailure(ParseFailure),
+    /// Subcommand failed: the final output along with the problems it is made of, parent parser
+    /// reports them together with its own problems, if any
+    Subcommand(ParseFailure, Vec<Problem>),
     /// Tried to consume a strict positional argument, value was present but was not strictly
     /// positional
     StrictPos(usize, Metavar),
 
     /// Parser provided by user failed to parse a value
-    ParseFailed(Option<usize>, String),
+    ParseFailed(Option<usize>, Doc),
 
     /// Parser provided by user failed to validate a value
-    GuardFailed(Option<usize>, &'static str),
+    GuardFailed(Option<usize>, Doc),
 
     /// Argument requres a value but something else was passed,
     /// required: --foo <BAR>
@@ -67,6 +73,10 @@ pub(crate) enum Message {
     /// argument is ambigoups - parser can accept it as both a set of flags and a short flag with no =
     Ambiguity(usize, String),
 
+    /// Long name or a command is a prefix to several known names
+    /// --ver given, --verbose and --version are accepted
+    AmbiguousPrefix(usize, Vec<String>),
+
     /// Suggested fixes for typos or missing input
     Suggestion(usize, Suggestion),
 
@@ -84,6 +94,8 @@ pub(crate) enum Message {
 impl Message {
     pub(crate) fn can_catch(&self) -> bool {
         match self {
+            // nothing was consumed so this can be treated as a missing item
+            Message::Cardinality(_, got, _, _) => *got == 0,
             Message::NoEnv(_)
             | Message::ParseSome(_)
             | Message::ParseFail(_)
@@ -94,9 +106,11 @@ impl Message {
             | Message::GuardFailed(_, _)
             | Message::Unconsumed(_)
             | Message::Ambiguity(_, _)
+            | Message::AmbiguousPrefix(_, _)
             | Message::Suggestion(_, _)
             | Message::Conflict(_, _)
             | Message::ParseFailure(_)
+            | Message::Subcommand(_, _)
             | Message::Expected(_, _)
             | Message::OnlyOnce(_, _)
             | Message::NoArgument(_, _) => false,
@@ -124,6 +138,8 @@ impl Message {
             // help output takes priority
             (a @ Message::ParseFailure(_), _) => a,
             (_, b @ Message::ParseFailure(_)) => b,
+            (a @ Message::Subcommand(_, _), _) => a,
+            (_, b @ Message::Subcommand(_, _)) => b,
 
             // combine missing elements
             (Message::Missing(mut a), Message::Missing(mut b)) => {
@@ -207,10 +223,29 @@ impl ParseFailure {
     /// Prints a message to `stdout` or `stderr` and returns the exit code
     #[allow(clippy::must_use_candidate)]
     pub fn exit_code(self) -> i32 {
+        self.print(false)
+    }
+
+    /// Same as [`exit_code`](ParseFailure::exit_code), but long `stdout` messages are shown in a
+    /// pager
+    ///
+    /// See [`pager`](crate::OptionParser::pager) for details
+    #[cfg(feature = "pager")]
+    #[allow(clippy::must_use_candidate)]
+    pub fn exit_code_with_pager(self) -> i32 {
+        self.print(true)
+    }
+
+    fn print(self, pager: bool) -> i32 {
         let color = Color::default();
         match self {
             ParseFailure::Stdout(msg, full) => {
-                println!("{}", msg.render_console(full, color));
+                let msg = msg.render_console(full, color);
+                #[cfg(feature = "pager")]
+                let pager = pager && crate::pager::page(&msg);
+                if !pager {
+                    println!("{}", msg);
+                }
                 0
             }
             ParseFailure::Completion(s) => {
@@ -243,9 +278,21 @@ fn check_conflicts(args: &State) -> Option<Message> {
     Some(Message::Conflict(winner, loser))
 }
 
-fn textual_part(args: &State, ix: Option<usize>) -> Option<std::borrow::Cow<str>> {
+/// Closest completion candidate for a value that failed to parse, if there is one
+fn value_hint(help: &mut Doc, args: &State, mix: Option<usize>) {
+    let hint = mix.and_then(|ix| args.value_hints.iter().find(|(i, _)| *i == ix));
+    if let Some((_, value)) = hint {
+        help.text("did you mean ");
+        help.token(Token::BlockStart(Block::TermRef));
+        help.literal(value);
+        help.token(Token::BlockEnd(Block::TermRef));
+        help.text("?");
+    }
+}
+
+fn textual_part(args: &State, ix: Option<usize>) -> Option<std::borrow::Cow<'_, str>> {
     match args.items.get(ix?)? {
-        Arg::Short(_, _, _) | Arg::Long(_, _, _) => None,
+        Arg::Short(_, _, _) | Arg::Long(_, _, _) | Arg::Single(_, _, _) => None,
         Arg::ArgWord(s) | Arg::Word(s) | Arg::PosWord(s) => Some(s.to_string_lossy()),
     }
 }
@@ -257,22 +304,151 @@ fn only_once(args: &State, cur: usize) -> Option<usize> {
     let mut iter = args.items[..cur].iter().rev();
     let offset = match args.items.get(cur)? {
         Arg::Short(s, _, _) => iter.position(|a| a.match_short(*s)),
-        Arg::Long(l, _, _) => iter.position(|a| a.match_long(l)),
+        Arg::Long(l, _, _) => iter.position(|a| a.match_long(l, args.names.as_deref())),
+        Arg::Single(l, _, _) => iter.position(|a| matches!(a, Arg::Single(s, _, _) if s == l)),
         Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => None,
     };
     Some(cur - offset? - 1)
 }
 
+/// Item left on the command line after a bounded repetition stopped at the maximum
+fn over_limit(args: &State, cur: usize) -> Option<Message> {
+    let arg = args.items.get(cur)?;
+    args.bounded.iter().find_map(|(meta, min, max)| {
+        let mut names = KnownNames::default();
+        meta.collect_names(&mut names);
+        let positional = Meta::first_item(meta).map_or(false, |i| i.is_pos());
+        let accepts = |arg: &Arg| match arg {
+            Arg::Short(s, _, _) => names.short_flags.contains(s) || names.short_args.contains(s),
+            Arg::Long(_, _, _) => names
+                .longs
+                .iter()
+                .any(|l| arg.match_long(l, args.names.as_deref())),
+            Arg::Single(s, _, _) => names.singles.contains(&s.as_str()),
+            Arg::Word(_) | Arg::PosWord(_) => positional,
+            Arg::ArgWord(_) => false,
+        };
+        if !accepts(arg) {
+            return None;
+        }
+        let extra = args.items_iter().filter(|(_, a)| accepts(a)).count();
+        Some(Message::Cardinality(meta.clone(), max + extra, *min, *max))
+    })
+}
+
+/// Description of a single problem with the command line
+#[derive(Debug, Clone)]
+pub(crate) struct Problem {
+    /// main message
+    doc: Doc,
+    /// suggested fix, a part of the message or a separate note
+    help: Doc,
+    /// items to underline in the command line, primary or not
+    spans: Vec<(usize, bool)>,
+}
+
+impl Problem {
+    /// Item the problem is about, if any
+    fn primary(&self) -> Option<usize> {
+        self.spans
+            .iter()
+            .find(|(_, primary)| *primary)
+            .map(|(ix, _)| *ix)
+    }
+
+    fn write_short(&self, doc: &mut Doc) {
+        doc.doc(&self.doc);
+        if !self.help.is_empty() {
+            doc.text(", ");
+            doc.doc(&self.help);
+        }
+    }
+}
+
 impl Message {
+    pub(crate) fn render(self, args: &State, meta: &Meta) -> ParseFailure {
+        match self.problems(args, meta) {
+            Ok(problems) => write_problems(&problems, args, meta),
+            Err(failure) => failure,
+        }
+    }
+
+    /// Render a failure of a subcommand, keeping the problems so the parent parser can report
+    /// them along with its own
+    pub(crate) fn render_subcommand(self, args: &State, meta: &Meta) -> Message {
+        match self.problems(args, meta) {
+            Ok(problems) => Message::Subcommand(write_problems(&problems, args, meta), problems),
+            Err(failure) => Message::ParseFailure(failure),
+        }
+    }
+
+    /// Describe this and all the other collected problems, `Err` if output is already rendered
+    fn problems(self, args: &State, meta: &Meta) -> Result<Vec<Problem>, ParseFailure> {
+        let extra = if args.max_errors > 1 {
+            args.errors.as_slice()
+        } else {
+            &[]
+        };
+        let mut problems: Vec<Problem> = Vec::new();
+        for msg in std::iter::once(self).chain(extra.iter().cloned()) {
+            let found = match msg {
+                Message::Subcommand(failure, _) if problems.is_empty() => return Err(failure),
+                Message::Subcommand(_, found) => found,
+                msg => match msg.describe(args, meta) {
+                    Ok(problem) => vec![problem],
+                    Err(failure) if problems.is_empty() => return Err(failure),
+                    // already rendered
+                    Err(ParseFailure::Stderr(doc)) => vec![Problem {
+                        doc,
+                        help: Doc::default(),
+                        spans: Vec::new(),
+                    }],
+                    Err(ParseFailure::Stdout(..) | ParseFailure::Completion(_)) => continue,
+                },
+            };
+            for problem in found {
+                // the same item can be reported by several parsers
+                if matches!(problem.primary(), Some(ix) if problems.iter().any(|p| p.primary() == Some(ix)))
+                {
+                    continue;
+                }
+                problems.push(problem);
+            }
+        }
+        Ok(problems)
+    }
     #[allow(clippy::too_many_lines)] // it's a huge match with lots of simple cases
-    pub(crate) fn render(mut self, args: &State, meta: &Meta) -> ParseFailure {
+    fn describe(mut self, args: &State, meta: &Meta) -> Result<Problem, ParseFailure> {
+        // improvements below look at the first item left on the command line, when several
+        // items are reported make sure it is the right one
+        let view;
+        let args = match self {
+            Message::Unconsumed(ix) if matches!(args.items_iter().next(), Some((first, _)) if first < ix) =>
+            {
+                let mut state = args.clone();
+                for (skip, _) in args.items_iter().take_while(|(skip, _)| *skip < ix) {
+                    state.remove(skip);
+                }
+                view = state;
+                &view
+            }
+            _ => args,
+        };
+
         // try to come up with a better error message for a few cases
+        if matches!(self, Message::Unconsumed(_) | Message::Missing(_)) {
+            if let Some((ix, names)) = args.ambiguous_prefix() {
+                self = Message::AmbiguousPrefix(ix, names);
+            }
+        }
         match self {
             Message::Unconsumed(ix) => {
                 if let Some(conflict) = check_conflicts(args) {
                     self = conflict;
                 } else if let Some((ix, suggestion)) = crate::meta_youmean::suggest(args, meta) {
                     self = Message::Suggestion(ix, suggestion);
+                } else if let Some(over) = over_limit(args, ix) {
+                    self = over;
                 } else if let Some(prev_ix) = only_once(args, ix) {
                     self = Message::OnlyOnce(prev_ix, ix);
                 }
@@ -284,9 +460,15 @@ impl Message {
         }
 
         let mut doc = Doc::default();
+        doc.slash = args.style == OptionStyle::Slash;
+        // suggested fix, a part of the message or a separate note
+        let mut help = Doc::default();
+        help.slash = doc.slash;
+        // items to underline in the command line, primary or not
+        let mut spans = Vec::new();
         match self {
             // already rendered
-            Message::ParseFailure(f) => return f,
+            Message::ParseFailure(f) | Message::Subcommand(f, _) => return Err(f),
 
             // this case is handled above
             Message::Missing(_) => {
@@ -295,6 +477,7 @@ impl Message {
 
             // Error: --foo is not expected in this context
             Message::Unconsumed(ix) => {
+                spans.push((ix, true));
                 let item = &args.items[ix];
                 doc.token(Token::BlockStart(Block::TermRef));
                 doc.write(item, Style::Invalid);
@@ -312,7 +495,8 @@ impl Message {
             }
 
             // Error: FOO expected to be  in the right side of --
-            Message::StrictPos(_ix, metavar) => {
+            Message::StrictPos(ix, metavar) => {
+                spans.push((ix, true));
                 doc.text("expected ");
                 doc.token(Token::BlockStart(Block::TermRef));
                 doc.metavar(metavar);
@@ -328,8 +512,36 @@ impl Message {
                 doc.text(s);
             }
 
+            // Error: expected 2 to 5 occurrences of FILE, got 1
+            Message::Cardinality(meta, got, min, max) => {
+                doc.text("expected ");
+                let count = if min == max {
+                    doc.text(&format!("exactly {}", min));
+                    min
+                } else if max == usize::MAX {
+                    doc.text(&format!("at least {}", min));
+                    min
+                } else if min == 0 {
+                    doc.text(&format!("at most {}", max));
+                    max
+                } else {
+                    doc.text(&format!("{} to {}", min, max));
+                    max
+                };
+                doc.text(if count == 1 {
+                    " occurrence of "
+                } else {
+                    " occurrences of "
+                });
+                doc.token(Token::BlockStart(Block::TermRef));
+                doc.write_meta(&meta, true);
+                doc.token(Token::BlockEnd(Block::TermRef));
+                doc.text(&format!(", got {}", got));
+            }
+
             // Error: couldn't parse FIELD: <FromStr message>
             Message::ParseFailed(mix, s) => {
+                spans.extend(mix.map(|ix| (ix, true)));
                 doc.text("couldn't parse");
                 if let Some(field) = textual_part(args, mix) {
                     doc.text(" ");
@@ -338,11 +550,13 @@ impl Message {
                     doc.token(Token::BlockEnd(Block::TermRef));
                 }
                 doc.text(": ");
-                doc.text(&s);
+                doc.doc(&s);
+                value_hint(&mut help, args, mix);
             }
 
             // Error: ( FIELD:  | check failed: ) <message from guard>
             Message::GuardFailed(mix, s) => {
+                spans.extend(mix.map(|ix| (ix, true)));
                 if let Some(field) = textual_part(args, mix) {
                     doc.token(Token::BlockStart(Block::TermRef));
                     doc.invalid(&field);
@@ -351,13 +565,16 @@ impl Message {
                 } else {
                     doc.text("check failed: ");
                 }
-                doc.text(s);
+                doc.doc(&s);
+                value_hint(&mut help, args, mix);
             }
 
             // Error: --foo requires an argument FOO, got a flag --bar, try --foo=-bar to use it as an argument
             // Error: --foo requires an argument FOO
             Message::NoArgument(x, mv) => match args.get(x + 1) {
-                Some(Arg::Short(_, _, os) | Arg::Long(_, _, os)) => {
+                Some(Arg::Short(_, _, os) | Arg::Long(_, _, os) | Arg::Single(_, _, os)) => {
+                    spans.push((x, false));
+                    spans.push((x + 1, true));
                     let arg = &args.items[x];
                     let os = &os.to_string_lossy();
 
@@ -372,16 +589,17 @@ impl Message {
                     doc.token(Token::BlockStart(Block::TermRef));
                     doc.write(os, Style::Invalid);
                     doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(", try ");
-                    doc.token(Token::BlockStart(Block::TermRef));
-                    doc.write(arg, Style::Literal);
-                    doc.literal("=");
-                    doc.write(os, Style::Literal);
-                    doc.token(Token::BlockEnd(Block::TermRef));
-                    doc.text(" to use it as an argument");
+                    help.text("try ");
+                    help.token(Token::BlockStart(Block::TermRef));
+                    help.write(arg, Style::Literal);
+                    help.literal("=");
+                    help.write(os, Style::Literal);
+                    help.token(Token::BlockEnd(Block::TermRef));
+                    help.text(" to use it as an argument");
                 }
                 // "Some" part of this branch is actually unreachable
                 Some(Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_)) | None => {
+                    spans.push((x, true));
                     let arg = &args.items[x];
                     doc.token(Token::BlockStart(Block::TermRef));
                     doc.write(arg, Style::Literal);
@@ -392,6 +610,25 @@ impl Message {
                     doc.token(Token::BlockEnd(Block::TermRef));
                 }
             },
+            // Error: `--ver` is ambiguous, could be `--verbose` or `--version`
+            Message::AmbiguousPrefix(ix, names) => {
+                spans.push((ix, true));
+                doc.token(Token::BlockStart(Block::TermRef));
+                doc.write(&args.items[ix], Style::Invalid);
+                doc.token(Token::BlockEnd(Block::TermRef));
+                doc.text(" is ambiguous, could be ");
+                for (ix, name) in names.iter().enumerate() {
+                    if ix + 1 == names.len() {
+                        doc.text(" or ");
+                    } else if ix > 0 {
+                        doc.text(", ");
+                    }
+                    doc.token(Token::BlockStart(Block::TermRef));
+                    doc.literal(name);
+                    doc.token(Token::BlockEnd(Block::TermRef));
+                }
+            }
+
             // Error: <message from pure_with>
             Message::PureFailed(s) => {
                 doc.text(&s);
@@ -399,6 +636,7 @@ impl Message {
             // Error: app supports -f as both an option and an option-argument, try to split -foo
             // into invididual options (-f -o ..) or use -f=oo syntax to disambiguate
             Message::Ambiguity(ix, name) => {
+                spans.push((ix, true));
                 let mut chars = name.chars();
                 let first = chars.next().unwrap();
                 let rest = chars.as_str();
@@ -437,12 +675,15 @@ impl Message {
             }
             // Error: No such (flag|argument|command), did you mean  ...
             Message::Suggestion(ix, suggestion) => {
+                spans.push((ix, true));
                 let actual = &args.items[ix].to_string();
                 match suggestion {
                     Suggestion::Variant(v) => {
                         let ty = match &args.items[ix] {
                             _ if actual.starts_with('-') => "flag",
-                            Arg::Short(_, _, _) | Arg::Long(_, _, _) => "flag",
+                            Arg::Short(_, _, _) | Arg::Long(_, _, _) | Arg::Single(_, _, _) => {
+                                "flag"
+                            }
                             Arg::ArgWord(_) => "argument value",
                             Arg::Word(_) | Arg::PosWord(_) => "command or positional",
                         };
@@ -453,23 +694,16 @@ impl Message {
                         doc.token(Token::BlockStart(Block::TermRef));
                         doc.invalid(actual);
                         doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(", did you mean ");
-                        doc.token(Token::BlockStart(Block::TermRef));
+                        help.text("did you mean ");
+                        help.token(Token::BlockStart(Block::TermRef));
 
                         match v {
-                            Variant::CommandLong(name) => doc.literal(name),
-                            Variant::Flag(ShortLong::Long(l) | ShortLong::ShortLong(_, l)) => {
-                                doc.literal("--");
-                                doc.literal(l);
-                            }
-                            Variant::Flag(ShortLong::Short(s)) => {
-                                doc.literal("-");
-                                doc.write_char(s, Style::Literal);
-                            }
+                            Variant::CommandLong(name) => help.literal(name),
+                            Variant::Flag(name) => help.write_shortlong(&name),
                         };
 
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        help.token(Token::BlockEnd(Block::TermRef));
+                        help.text("?");
                     }
                     Suggestion::MissingDash(name) => {
                         doc.text("no such flag: ");
@@ -477,12 +711,13 @@ impl Message {
                         doc.literal("-");
                         doc.literal(name);
                         doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(" (with one dash), did you mean ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal("--");
-                        doc.literal(name);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        doc.text(" (with one dash)");
+                        help.text("did you mean ");
+                        help.token(Token::BlockStart(Block::TermRef));
+                        help.literal("--");
+                        help.literal(name);
+                        help.token(Token::BlockEnd(Block::TermRef));
+                        help.text("?");
                     }
                     Suggestion::ExtraDash(name) => {
                         doc.text("no such flag: ");
@@ -490,12 +725,13 @@ impl Message {
                         doc.literal("--");
                         doc.write_char(name, Style::Literal);
                         doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(" (with two dashes), did you mean ");
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal("-");
-                        doc.write_char(name, Style::Literal);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        doc.text(" (with two dashes)");
+                        help.text("did you mean ");
+                        help.token(Token::BlockStart(Block::TermRef));
+                        help.literal("-");
+                        help.write_char(name, Style::Literal);
+                        help.token(Token::BlockEnd(Block::TermRef));
+                        help.text("?");
                     }
                     Suggestion::Nested(x, v) => {
                         let ty = match v {
@@ -507,18 +743,18 @@ impl Message {
                         doc.token(Token::BlockStart(Block::TermRef));
                         doc.literal(actual);
                         doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text(
-                            " is not valid in this context, did you mean to pass it to command ",
-                        );
-                        doc.token(Token::BlockStart(Block::TermRef));
-                        doc.literal(&x);
-                        doc.token(Token::BlockEnd(Block::TermRef));
-                        doc.text("?");
+                        doc.text(" is not valid in this context");
+                        help.text("did you mean to pass it to command ");
+                        help.token(Token::BlockStart(Block::TermRef));
+                        help.literal(&x);
+                        help.token(Token::BlockEnd(Block::TermRef));
+                        help.text("?");
                     }
                 }
             }
             // Error: Expected (no arguments|--foo), got ..., pass --help
             Message::Expected(exp, actual) => {
+                spans.extend(actual.map(|ix| (ix, true)));
                 doc.text("expected ");
                 match exp.len() {
                     0 => {
@@ -562,13 +798,15 @@ impl Message {
                     }
                 }
                 doc.token(Token::BlockStart(Block::TermRef));
-                doc.literal("--help");
+                doc.write_shortlong(&ShortLong::Long("help"));
                 doc.token(Token::BlockEnd(Block::TermRef));
                 doc.text(" for usage information");
             }
 
             // Error: --intel cannot be used at the same time as --att
             Message::Conflict(winner, loser) => {
+                spans.push((winner, false));
+                spans.push((loser, true));
                 doc.token(Token::BlockStart(Block::TermRef));
                 doc.write(&args.items[loser], Style::Literal);
                 doc.token(Token::BlockEnd(Block::TermRef));
@@ -579,7 +817,9 @@ impl Message {
             }
 
             // Error: argument FOO cannot be used multiple times in this context
-            Message::OnlyOnce(_winner, loser) => {
+            Message::OnlyOnce(winner, loser) => {
+                spans.push((winner, false));
+                spans.push((loser, true));
                 doc.text("argument ");
                 doc.token(Token::BlockStart(Block::TermRef));
                 doc.write(&args.items[loser], Style::Literal);
@@ -588,8 +828,159 @@ impl Message {
             }
         };
 
-        ParseFailure::Stderr(doc)
+        Ok(Problem { doc, help, spans })
+    }
+}
+
+/// Command line reconstructed from parsed items along with columns taken by every item
+///
+/// Items produced by splitting a single word such as `-vf` or `--key=value` point to their part
+/// of the word
+fn command_line(items: &[Arg]) -> (String, Vec<Range<usize>>) {
+    let mut line = String::new();
+    let mut ranges = Vec::with_capacity(items.len());
+    // current word: original text, columns it takes and the column after the last short flag
+    let mut word: &std::ffi::OsStr = std::ffi::OsStr::new("");
+    let mut word_end = 0;
+    let mut cursor = 0;
+    let mut prev: Option<&Arg> = None;
+    for item in items {
+        let continues = match (prev, item) {
+            (Some(Arg::Short(_, false, _)), Arg::Short(_, _, os)) => {
+                os.is_empty() || (os == word && cursor < word_end)
+            }
+            (
+                Some(Arg::Short(_, true, _) | Arg::Long(_, true, _)),
+                Arg::ArgWord(_) | Arg::Word(_),
+            ) => true,
+            _ => false,
+        };
+        let range = match item {
+            Arg::Short(c, _, _) if continues => {
+                let start = cursor;
+                cursor += str_width(c.encode_utf8(&mut [0; 4]));
+                start..cursor
+            }
+            _ if continues => {
+                let width = str_width(&item.os_str().to_string_lossy());
+                word_end - width..word_end
+            }
+            _ => {
+                word = item.os_str();
+                let text = word.to_string_lossy();
+                let quote = text.is_empty() || text.contains(char::is_whitespace);
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                let start = str_width(&line) + usize::from(quote);
+                word_end = start + str_width(&text);
+                if quote {
+                    line.push_str(&format!("'{}'", text));
+                } else {
+                    line.push_str(&text);
+                }
+                cursor = match item {
+                    Arg::Short(c, _, _) => start + 1 + str_width(c.encode_utf8(&mut [0; 4])),
+                    Arg::Long(_, true, _) => {
+                        let name = text.find(|c| c == '=' || c == ':').unwrap_or(text.len());
+                        start + str_width(&text[..name])
+                    }
+                    _ => word_end,
+                };
+                start..cursor
+            }
+        };
+        ranges.push(range);
+        prev = Some(item);
+    }
+    (line, ranges)
+}
+
+/// Problem description followed by the command line with offending items underlined and a
+/// suggested fix
+/// Render up to `max_errors` problems, mentioning how many more there are
+fn write_problems(problems: &[Problem], args: &State, meta: &Meta) -> ParseFailure {
+    let shown = &problems[..problems.len().min(args.max_errors)];
+    let more = match problems.len() - shown.len() {
+        0 => None,
+        1 => Some(String::from("and 1 more problem")),
+        n => Some(format!("and {} more problems", n)),
+    };
+
+    let mut doc = Doc::default();
+    doc.slash = args.style == OptionStyle::Slash;
+    if args.error_style == ErrorStyle::Diagnostic {
+        for problem in shown {
+            write_diagnostic(&mut doc, args, problem);
+        }
+        if let Some(more) = more {
+            doc.token(Token::BlockStart(Block::Block));
+            doc.text(&more);
+            doc.token(Token::BlockEnd(Block::Block));
+        }
+        doc.emphasis("usage");
+        doc.text(": ");
+        doc.token(Token::BlockStart(Block::Mono));
+        doc.write_path(&args.path);
+        doc.write_usage(meta, args.layout.usage);
+        doc.token(Token::BlockEnd(Block::Mono));
+    } else if let [problem] = problems {
+        problem.write_short(&mut doc);
+    } else {
+        doc.text(&format!("{} problems found:", problems.len()));
+        doc.bullet_list(
+            shown
+                .iter()
+                .map(|problem| {
+                    let mut doc = Doc::default();
+                    problem.write_short(&mut doc);
+                    doc
+                })
+                .chain(more.map(Doc::from)),
+        );
+    }
+    ParseFailure::Stderr(doc)
+}
+
+fn write_diagnostic(doc: &mut Doc, args: &State, problem: &Problem) {
+    doc.token(Token::BlockStart(Block::Block));
+    doc.doc(&problem.doc);
+    if !problem.spans.is_empty() {
+        let (line, ranges) = command_line(&args.items);
+        let mut spans = problem
+            .spans
+            .iter()
+            .filter_map(|(ix, primary)| Some((ranges.get(*ix)?.clone(), *primary)))
+            .collect::<Vec<_>>();
+        spans.sort_by_key(|(range, _)| range.start);
+
+        doc.token(Token::BlockStart(Block::CodeBlock));
+        doc.text(&line);
+        doc.text("\n");
+        let mut col = 0;
+        for (range, primary) in spans {
+            if range.start < col {
+                continue;
+            }
+            let width = (range.end - range.start).max(1);
+            doc.text(&" ".repeat(range.start - col));
+            if primary {
+                doc.write_str(&"^".repeat(width), Style::Invalid);
+            } else {
+                doc.write_str(&"-".repeat(width), Style::Literal);
+            }
+            col = range.start + width;
+        }
+        doc.token(Token::BlockEnd(Block::CodeBlock));
+    }
+
+    if !problem.help.is_empty() {
+        doc.text("\n ");
+        doc.emphasis("help");
+        doc.text(": ");
+        doc.doc(&problem.help);
     }
+    doc.token(Token::BlockEnd(Block::Block));
 }
 
 /// go over all the missing items, pick the left most scope
@@ -618,7 +1009,9 @@ pub(crate) fn summarize_missing(items: &[MissingItem], inner: &Meta, args: &Stat
     best_scope.start = best_scope.start.max(best_item.position);
     let mut args = args.clone();
     args.set_scope(best_scope);
-    if let Some((ix, _arg)) = args.items_iter().next() {
+    // with several errors reported at once items left on the command line get their own messages
+    let got = args.items_iter().next().filter(|_| args.max_errors == 1);
+    if let Some((ix, _arg)) = got {
         if let Some((ix, sugg)) = crate::meta_youmean::suggest(&args, inner) {
             Message::Suggestion(ix, sugg)
         } else {
@@ -1,9 +1,10 @@
 //! Help message generation and rendering
 
 use crate::{
-    args::{Args, State},
+    args::{Arg, Args, KnownNames, NameMatch, State},
     error::Message,
-    meta_help::render_help,
+    item::Item,
+    meta_help::{render_help, render_search, HelpContext},
     parsers::NamedArg,
     short, Doc, Error, Meta, ParseFailure, Parser,
 };
@@ -27,6 +28,143 @@ pub struct Info {
     pub help_arg: NamedArg,
     pub version_arg: NamedArg,
     pub help_if_no_args: bool,
+    /// Accept unique prefixes of long names and commands, see [`abbreviations`][OptionParser::abbreviations]
+    pub abbreviations: bool,
+    /// Match long names and commands ignoring case, see [`ignore_case`][OptionParser::ignore_case]
+    pub ignore_case: bool,
+    /// Treat `_` and `-` in long names and commands as the same character,
+    /// see [`underscores_as_dashes`][OptionParser::underscores_as_dashes]
+    pub underscores_as_dashes: bool,
+    /// Prefix used for named items, see [`option_style`][OptionParser::option_style]
+    pub option_style: OptionStyle,
+    /// Stop parsing options at the first positional item, see [`options_first`][OptionParser::options_first]
+    pub options_first: bool,
+    /// Stop parsing options at the first positional item if `POSIXLY_CORRECT` is set,
+    /// see [`posixly_correct`][OptionParser::posixly_correct]
+    pub posixly_correct: bool,
+    /// Accept `help` subcommand, see [`help_command`][OptionParser::help_command]
+    pub help_command: bool,
+    /// Search help of all the subcommands with `--help KEYWORD`,
+    /// see [`help_search`][OptionParser::help_search]
+    pub help_search: bool,
+    /// Show long help in a pager, see [`pager`][OptionParser::pager]
+    #[cfg(feature = "pager")]
+    pub pager: bool,
+    /// Custom sections for generated documentation, see [`section`][OptionParser::section]
+    pub sections: Vec<(&'static str, Doc)>,
+    /// Add `EXIT STATUS` and `ENVIRONMENT` sections to generated documentation,
+    /// see [`generated_sections`][OptionParser::generated_sections]
+    pub generated_sections: bool,
+    /// Usage examples, see [`example`][OptionParser::example]
+    pub examples: Vec<(&'static str, Doc)>,
+    /// Help message layout, see [`help_order`][OptionParser::help_order],
+    /// [`section_title`][OptionParser::section_title] and [`help_columns`][OptionParser::help_columns]
+    pub layout: HelpLayout,
+    /// Error message presentation, see [`error_style`][OptionParser::error_style]
+    pub error_style: ErrorStyle,
+    /// Number of errors to report at once, see [`max_errors`][OptionParser::max_errors]
+    pub max_errors: usize,
+    /// Prefix of environment variables to check for typos, see [`env_prefix`][OptionParser::env_prefix]
+    pub env_prefix: Option<&'static str>,
+    /// Print environment variable warnings in [`run`][OptionParser::run],
+    /// see [`print_env_warnings`][OptionParser::print_env_warnings]
+    pub print_env_warnings: bool,
+}
+
+/// Presentation of parse errors, see [`error_style`](OptionParser::error_style)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorStyle {
+    /// A single sentence describing the problem, default
+    Short,
+    /// Problem description followed by the command line with offending items underlined, usage
+    /// of the failing (sub)command and suggestions for a fix, if any
+    Diagnostic,
+}
+
+/// Order of items within a help section, see [`help_order`](OptionParser::help_order)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpOrder {
+    /// Items are listed in the order they are declared in the parser, default
+    Declaration,
+    /// Items are sorted by their long name, short name, metavariable or command name
+    Alphabetical,
+}
+
+/// Help section with a configurable title, see [`section_title`](OptionParser::section_title)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpSection {
+    /// Positional items, `Available positional items:` by default
+    Positionals,
+    /// Named items, `Available options:` by default
+    Options,
+    /// Subcommands, `Available commands:` by default
+    Commands,
+}
+
+/// Placement of help next to item names, see [`help_columns`](OptionParser::help_columns)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpColumns {
+    /// Help is aligned in a column next to item names, help for names too long for the column
+    /// starts on the same line right after the name, default
+    Aligned,
+    /// Help is aligned in a column next to item names, help for names too long for the column
+    /// starts on the next line
+    TwoLine,
+}
+
+/// Rendering of the generated usage line, see [`usage_style`](OptionParser::usage_style)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageStyle {
+    /// Every item is listed explicitly, default
+    Full,
+    /// Optional named items are collapsed into a single `[OPTIONS]`
+    Compact,
+}
+
+/// Presentation of the help message
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct HelpLayout {
+    pub order: HelpOrder,
+    /// title of positional items section
+    pub positionals_title: &'static str,
+    /// title of options section
+    pub options_title: &'static str,
+    /// title of commands section
+    pub commands_title: &'static str,
+    pub columns: HelpColumns,
+    pub usage: UsageStyle,
+}
+
+impl Default for HelpLayout {
+    fn default() -> Self {
+        Self {
+            order: HelpOrder::Declaration,
+            positionals_title: "Available positional items:",
+            options_title: "Available options:",
+            commands_title: "Available commands:",
+            columns: HelpColumns::Aligned,
+            usage: UsageStyle::Full,
+        }
+    }
+}
+
+/// Prefix style used for named items, see [`option_style`](OptionParser::option_style)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionStyle {
+    /// Unix style names with dashes: `-f`, `--flag` and `--flag=value`, default
+    Dash,
+    /// Slash style names in addition to dashes: `/f`, `/flag`, `/flag:value` and `/flag=value`,
+    /// help and usage are rendered with dashes
+    DashAndSlash,
+    /// Slash style names only, help and usage are rendered with slashes as well
+    Slash,
+}
+
+impl Default for OptionStyle {
+    fn default() -> Self {
+        OptionStyle::Dash
+    }
 }
 
 impl Default for Info {
@@ -42,6 +180,24 @@ impl Default for Info {
                 .long("version")
                 .help("Prints version information"),
             help_if_no_args: false,
+            abbreviations: false,
+            ignore_case: false,
+            underscores_as_dashes: false,
+            option_style: OptionStyle::Dash,
+            options_first: false,
+            posixly_correct: false,
+            help_command: false,
+            help_search: false,
+            #[cfg(feature = "pager")]
+            pager: false,
+            sections: Vec::new(),
+            generated_sections: true,
+            examples: Vec::new(),
+            layout: HelpLayout::default(),
+            error_style: ErrorStyle::Short,
+            max_errors: 1,
+            env_prefix: None,
+            print_env_warnings: true,
         }
     }
 }
@@ -86,7 +242,16 @@ impl<T> OptionParser<T> {
         Self: Sized,
     {
         match self.run_inner(Args::current_args()) {
-            Ok(t) => t,
+            Ok(t) => {
+                if self.info.print_env_warnings {
+                    for warning in self.env_warnings() {
+                        eprintln!("Warning: {}", warning);
+                    }
+                }
+                t
+            }
+            #[cfg(feature = "pager")]
+            Err(err) if self.info.pager => std::process::exit(err.exit_code_with_pager()),
             Err(err) => std::process::exit(err.exit_code()),
         }
     }
@@ -194,27 +359,66 @@ impl<T> OptionParser<T> {
         Self: Sized,
     {
         // prepare available short flags and arguments for disambiguation
-        let mut short_flags = Vec::new();
-        let mut short_args = Vec::new();
-        self.inner
-            .meta()
-            .collect_shorts(&mut short_flags, &mut short_args);
-        short_flags.extend(&self.info.help_arg.short);
-        short_flags.extend(&self.info.version_arg.short);
+        let mut names = KnownNames {
+            style: self.info.option_style,
+            options_first: self.info.options_first
+                || (self.info.posixly_correct && std::env::var_os("POSIXLY_CORRECT").is_some()),
+            relaxed: NameMatch::new(&self.inner.meta(), &self.info, None),
+            ..KnownNames::default()
+        };
+        self.inner.meta().collect_names(&mut names);
+        for named in [&self.info.help_arg, &self.info.version_arg] {
+            names.short_flags.extend(&named.short);
+            names.longs.extend(&named.long);
+        }
         let args = args.into();
         let mut err = None;
-        let mut state = State::construct(args, &short_flags, &short_args, &mut err);
+        let mut state = State::construct(args, &names, &mut err);
+        state.layout = self.info.layout;
+        state.error_style = self.info.error_style;
+        state.max_errors = self.info.max_errors;
 
         // this only handles disambiguation failure in construct
         if let Some(msg) = err {
             return Err(msg.render(&state, &self.inner.meta()));
         }
 
-        self.run_subparser(&mut state)
+        self.run_subparser(&mut state).map_err(|err| match err {
+            Message::ParseFailure(failure) | Message::Subcommand(failure, _) => failure,
+            err => err.render(&state, &self.inner.meta()),
+        })
     }
 
     /// Run subparser, implementation detail
-    pub(crate) fn run_subparser(&self, args: &mut State) -> Result<T, ParseFailure> {
+    ///
+    /// Fails with [`Message::ParseFailure`] or [`Message::Subcommand`]
+    pub(crate) fn run_subparser(&self, args: &mut State) -> Result<T, Message> {
+        // names available for relaxed matching are different for every subparser and
+        // parent parser might want to continue parsing after the subparser is done
+        let parent_names = args.names.clone();
+        args.names = NameMatch::new(&self.inner.meta(), &self.info, parent_names.as_deref())
+            .map(std::rc::Rc::new);
+        let parent_reserved = std::mem::replace(&mut args.reserved, self.info.reserved());
+        // subparser failures are reported by the subparser itself
+        let parent_errors = std::mem::take(&mut args.errors);
+
+        // global items of the parent parsers are listed in help, own ones are passed further
+        let parent_globals = args.globals.clone();
+        let mut globals = parent_globals.to_vec();
+        self.inner.meta().collect_globals(&mut globals);
+        if globals.len() != parent_globals.len() {
+            args.globals = globals.into();
+        }
+
+        let res = self.run_subparser_inner(args, &parent_globals);
+        args.names = parent_names;
+        args.reserved = parent_reserved;
+        args.globals = parent_globals;
+        args.errors = parent_errors;
+        res
+    }
+
+    fn run_subparser_inner(&self, args: &mut State, globals: &[Item]) -> Result<T, Message> {
         // process should work like this:
         // - inner parser is evaluated, it returns Error
         // - if error is finalized (ParseFailure) - it is simply propagated outwards,
@@ -225,31 +429,42 @@ impl<T> OptionParser<T> {
         // - produce --help, --version
         // - Try to improve error message and finalize it otherwise
         //
-        // outer parser gets value in ParseFailure format
+        // outer parser gets value in ParseFailure format, along with the problems for
+        // subcommand failures
 
         if self.info.help_if_no_args && args.is_empty() {
             let buffer = render_help(
                 &args.path,
                 &self.info,
                 &self.inner.meta(),
-                &self.info.meta(),
+                &self.info.help_meta(globals),
                 true,
+                HelpContext::from(&*args),
             );
-            return Err(ParseFailure::Stdout(buffer, false));
+            return Err(Message::ParseFailure(ParseFailure::Stdout(buffer, false)));
         };
 
+        // parser runs again when looking for replacements of values that failed to parse
+        #[cfg(feature = "autocomplete")]
+        let orig = args.clone();
+        let depth = args.depth();
+
         let res = self.inner.eval(args);
-        if let Err(Error(Message::ParseFailure(failure))) = res {
-            return Err(failure);
+        if let Err(Error(err @ (Message::ParseFailure(_) | Message::Subcommand(_, _)))) = res {
+            return Err(err);
+        }
+        #[cfg(feature = "autocomplete")]
+        if self.info.help_command && args.is_comp() {
+            self.complete_help_command(args);
         }
         #[cfg(feature = "autocomplete")]
         if let Some(comp) = args.check_complete() {
-            return Err(ParseFailure::Completion(comp));
+            return Err(Message::ParseFailure(ParseFailure::Completion(comp)));
         }
 
         let err = match res {
             Ok(ok) => {
-                if let Some((ix, _)) = args.items_iter().next() {
+                if let Some(ix) = args.first_non_global(globals) {
                     Message::Unconsumed(ix)
                 } else {
                     return Ok(ok);
@@ -264,13 +479,41 @@ impl<T> OptionParser<T> {
             let buffer = match extra {
                 ExtraParams::Help(d) => {
                     detailed = d;
-                    render_help(
+                    let mut buffer = render_help(
                         &args.path,
                         &self.info,
                         &self.inner.meta(),
-                        &self.info.meta(),
+                        &self.info.help_meta(globals),
                         true,
-                    )
+                        HelpContext::from(&*args),
+                    );
+                    if detailed && !self.info.examples.is_empty() {
+                        use crate::buffer::{Block, Style, Token};
+                        buffer.token(Token::BlockStart(Block::Block));
+                        buffer.token(Token::BlockStart(Block::Section2));
+                        buffer.write_str("Examples:", Style::Emphasis);
+                        buffer.token(Token::BlockEnd(Block::Section2));
+                        buffer.write_examples(&args.path, &self.info.examples);
+                        buffer.token(Token::BlockEnd(Block::Block));
+                    }
+                    buffer
+                }
+                ExtraParams::HelpCommand(words) => self
+                    .render_help_command(args, globals, &words)
+                    .map_err(Message::ParseFailure)?,
+                ExtraParams::Search(keyword) => {
+                    match render_search(&args.path, &self.inner.meta(), &keyword, args.style) {
+                        Some(buffer) => buffer,
+                        None => {
+                            use crate::buffer::{Block, Token};
+                            let mut buffer = Doc::default();
+                            buffer.text("no help items match ");
+                            buffer.token(Token::BlockStart(Block::TermRef));
+                            buffer.invalid(&keyword);
+                            buffer.token(Token::BlockEnd(Block::TermRef));
+                            return Err(Message::ParseFailure(ParseFailure::Stderr(buffer)));
+                        }
+                    }
                 }
                 ExtraParams::Version(v) => {
                     use crate::buffer::{Block, Token};
@@ -282,9 +525,204 @@ impl<T> OptionParser<T> {
                     buffer
                 }
             };
-            return Err(ParseFailure::Stdout(buffer, detailed));
+            return Err(Message::ParseFailure(ParseFailure::Stdout(
+                buffer, detailed,
+            )));
+        }
+        if args.max_errors > 1 {
+            args.collect_unconsumed(globals);
+        }
+        #[cfg(feature = "autocomplete")]
+        self.suggest_values(&orig, args, &err);
+        // failed subcommands leave their names in the path, usage is for this parser
+        let nested = args.path.split_off(depth);
+        let err = err.render_subcommand(args, &self.inner.meta());
+        args.path.extend(nested);
+        Err(err)
+    }
+
+    /// Look for replacements of values that failed to parse or validate among completion
+    /// candidates
+    ///
+    /// Parser runs once more from the original state with the value replaced by an empty
+    /// string so [`complete`](Parser::complete) functions produce all the candidates, values
+    /// without a completion function are skipped
+    #[cfg(feature = "autocomplete")]
+    fn suggest_values(&self, orig: &State, args: &mut State, err: &Message) {
+        let failed = std::iter::once(err)
+            .chain(&args.errors)
+            .filter_map(|msg| match msg {
+                Message::ParseFailed(Some(ix), _) | Message::GuardFailed(Some(ix), _) => Some(*ix),
+                _ => None,
+            })
+            .filter(|ix| args.completed.contains(ix))
+            .collect::<Vec<_>>();
+        for ix in failed {
+            let mut state = match orig.blank_value(ix) {
+                Some(state) => state,
+                None => continue,
+            };
+            state.candidates = Some((ix, Vec::new()));
+            let _ = self.inner.eval(&mut state);
+            let candidates = state.candidates.map(|c| c.1).unwrap_or_default();
+            let actual = args.items[ix].os_str().to_string_lossy();
+            if let Some(best) = crate::meta_youmean::closest(&actual, candidates) {
+                args.value_hints.push((ix, best));
+            }
+        }
+    }
+
+    /// Environment variables that look like typos in names declared with [`env`](NamedArg::env)
+    ///
+    /// Only variables that start with the prefix set by [`env_prefix`](OptionParser::env_prefix)
+    /// are checked, [`run`](OptionParser::run) prints those warnings to stderr after a successful
+    /// parse unless disabled with [`print_env_warnings`](OptionParser::print_env_warnings).
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// std::env::set_var("DEMO_APP_JOSB", "4");
+    /// let jobs = long("jobs").env("DEMO_APP_JOBS").argument::<usize>("N").fallback(1);
+    /// let parser = jobs.to_options().env_prefix("DEMO_APP_");
+    ///
+    /// assert_eq!(parser.run_inner(&[]).unwrap(), 1);
+    /// assert_eq!(
+    ///     parser.env_warnings(),
+    ///     ["environment variable `DEMO_APP_JOSB` is not used, did you mean `DEMO_APP_JOBS`?"]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn env_warnings(&self) -> Vec<String> {
+        let prefix = match self.info.env_prefix {
+            Some(prefix) => prefix,
+            None => return Vec::new(),
+        };
+        let mut names = KnownNames::default();
+        self.inner.meta().collect_names(&mut names);
+        let mut res = std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .filter(|name| name.starts_with(prefix) && !names.envs.contains(&name.as_str()))
+            .filter_map(|name| {
+                let best = crate::meta_youmean::closest(&name, names.envs.iter().copied())?;
+                Some(format!(
+                    "environment variable `{}` is not used, did you mean `{}`?",
+                    name, best
+                ))
+            })
+            .collect::<Vec<_>>();
+        res.sort();
+        res
+    }
+
+    /// Render help for a nested subcommand: `app help build deploy`
+    ///
+    /// `words` are indices of subcommand names in `args`
+    fn render_help_command(
+        &self,
+        args: &State,
+        globals: &[Item],
+        words: &[usize],
+    ) -> Result<Doc, ParseFailure> {
+        let root = self.inner.meta();
+        let mut meta = &root;
+        let mut info = &self.info;
+        let mut path = args.path.clone();
+        let mut globals = globals.to_vec();
+        // names are matched the same way as when running the subcommand
+        let mut relaxed = args.names.clone();
+        let mut commands = Vec::new();
+        for &ix in words {
+            commands.clear();
+            meta.collect_commands(&mut commands);
+            let word = args.items[ix].os_str().to_str();
+            let same = |name: &str| match (word, relaxed.as_deref()) {
+                (Some(w), Some(r)) => r.command_matches(name, w),
+                _ => false,
+            };
+            let found = commands.iter().find_map(|item| match item {
+                Item::Command {
+                    name,
+                    short,
+                    meta,
+                    info,
+                    ..
+                } if word == Some(*name)
+                    || word == short.map(String::from).as_deref()
+                    || same(name) =>
+                {
+                    Some((*name, meta.as_ref(), info.as_ref()))
+                }
+                _ => None,
+            });
+            match found {
+                Some((name, inner_meta, inner_info)) => {
+                    relaxed = NameMatch::new(inner_meta, inner_info, relaxed.as_deref())
+                        .map(std::rc::Rc::new);
+                    meta.collect_globals(&mut globals);
+                    path.push(name.to_owned());
+                    meta = inner_meta;
+                    info = inner_info;
+                }
+                None => {
+                    // narrow the scope so error message talks about this name only
+                    let mut args = args.clone();
+                    args.set_scope(ix..ix + 1);
+                    return Err(Message::Unconsumed(ix).render(&args, meta));
+                }
+            }
+        }
+        Ok(render_help(
+            &path,
+            info,
+            meta,
+            &info.help_meta(&globals),
+            true,
+            HelpContext::from(args),
+        ))
+    }
+
+    /// Offer `help` and subcommand names after it for completion
+    #[cfg(feature = "autocomplete")]
+    fn complete_help_command(&self, args: &mut State) {
+        let mut items = args.items_iter();
+        let (first, is_help) = match items.next() {
+            Some((ix, arg)) => (ix, arg.os_str() == "help"),
+            None => return,
+        };
+        let words = items.map(|(ix, _)| ix).collect::<Vec<_>>();
+        if !is_help {
+            if first + 1 == args.items.len() {
+                args.push_command("help", None, &Some(Info::help_command_help()));
+            }
+            return;
+        }
+
+        // walk down to the last complete subcommand name and offer everything it contains
+        let root = self.inner.meta();
+        let mut meta = &root;
+        let mut commands = Vec::new();
+        for &ix in words.iter().take(words.len().saturating_sub(1)) {
+            commands.clear();
+            meta.collect_commands(&mut commands);
+            let word = args.items[ix].os_str();
+            match commands.iter().find_map(|item| match item {
+                Item::Command { name, meta, .. } if word == *name => Some(meta.as_ref()),
+                _ => None,
+            }) {
+                Some(inner) => meta = inner,
+                None => return,
+            }
+        }
+        commands.clear();
+        meta.collect_commands(&mut commands);
+        args.clear_comps();
+        for item in commands {
+            if let Item::Command {
+                name, short, help, ..
+            } = item
+            {
+                args.push_command(name, *short, help);
+            }
         }
-        Err(err.render(args, &self.inner.meta()))
     }
 
     /// Get first line of description if Available
@@ -532,6 +970,161 @@ impl<T> OptionParser<T> {
         self
     }
 
+    /// Add a custom section to generated documentation
+    ///
+    /// Sections such as `FILES`, `AUTHORS` or `SEE ALSO` are rendered after the options and
+    /// automatically generated `EXIT STATUS` and `ENVIRONMENT` sections by
+    /// [`render_manpage`](OptionParser::render_manpage),
+    /// [`render_markdown`](OptionParser::render_markdown) and
+    /// [`render_html`](OptionParser::render_html) in the order they were added. `SEE ALSO`
+    /// always goes last, after links to other pages, if any. A section named `EXIT STATUS` or
+    /// `ENVIRONMENT`, ignoring case, replaces the generated one. `--help` output is not
+    /// affected.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let parser = construct!(verbose)
+    ///     .to_options()
+    ///     .section("FILES", "~/.config/app.toml")
+    ///     .section("AUTHORS", "Written by the app team");
+    /// ```
+    #[must_use]
+    pub fn section<M: Into<Doc>>(mut self, name: &'static str, body: M) -> Self {
+        self.info.sections.push((name, body.into()));
+        self
+    }
+
+    /// Add automatically generated sections to generated documentation
+    ///
+    /// Enabled by default: documentation lists exit codes in `EXIT STATUS` and environment
+    /// variables used by any of the parsers in `ENVIRONMENT`. Setting is used by the top level
+    /// parser only and applies to all the nested subcommands as well. Custom sections added
+    /// with [`section`](OptionParser::section) are not affected.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let parser = construct!(verbose)
+    ///     .to_options()
+    ///     .generated_sections(false);
+    /// ```
+    #[must_use]
+    pub fn generated_sections(mut self, enabled: bool) -> Self {
+        self.info.generated_sections = enabled;
+        self
+    }
+
+    /// Add a usage example: a command line along with an explanation
+    ///
+    /// Command line is given without the application name and for subcommands without the
+    /// subcommand names. Examples are listed in detailed help (`--help --help`) and in the
+    /// EXAMPLES section of generated documentation. Use
+    /// [`check_examples`](OptionParser::check_examples) in a test to make sure they stay valid.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let parser = construct!(verbose)
+    ///     .to_options()
+    ///     .example("-v", "Run with verbose output");
+    /// let r = parser.run_inner(&["--help", "--help"]).unwrap_err().unwrap_stdout();
+    /// assert!(r.contains("Examples:"));
+    /// assert!(r.contains("Run with verbose output"));
+    /// ```
+    #[must_use]
+    pub fn example<M: Into<Doc>>(mut self, command: &'static str, explanation: M) -> Self {
+        self.info.examples.push((command, explanation.into()));
+        self
+    }
+
+    /// Check that all the examples of the parser and its subcommands still parse
+    ///
+    /// Every example added with [`example`](OptionParser::example) is split into words the way
+    /// a POSIX shell does it: on whitespace, except for whitespace inside of single or double
+    /// quotes or escaped with a backslash, so `--name "John Smith"` passes a single value.
+    /// Words are prefixed with subcommand names if needed and passed to
+    /// [`run_inner`](OptionParser::run_inner). Examples that print help or version
+    /// information are accepted, examples with unterminated quotes are not. Variables,
+    /// globs and other shell expansions are not performed.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a list of examples that fail to parse, this function is meant to be used in
+    /// tests
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').switch();
+    /// let name = long("name").argument::<String>("NAME").optional();
+    /// let parser = construct!(verbose, name)
+    ///     .to_options()
+    ///     .example("-v", "Verbose output")
+    ///     .example("--name 'John Smith'", "Greet John");
+    /// parser.check_examples();
+    /// ```
+    #[track_caller]
+    pub fn check_examples(&self) {
+        fn collect<'a>(
+            meta: &'a Meta,
+            info: &'a Info,
+            prefix: &mut Vec<&'static str>,
+            res: &mut Vec<(String, Result<Vec<String>, String>)>,
+        ) {
+            for (command, _) in &info.examples {
+                let mut example = prefix.join(" ");
+                if !example.is_empty() {
+                    example.push(' ');
+                }
+                example.push_str(command);
+                let words = split_shell_words(command).map(|words| {
+                    let mut res = prefix.iter().map(|w| (*w).to_owned()).collect::<Vec<_>>();
+                    res.extend(words);
+                    res
+                });
+                res.push((example, words));
+            }
+            let mut commands = Vec::new();
+            meta.collect_commands(&mut commands);
+            for item in commands {
+                if let Item::Command {
+                    name, meta, info, ..
+                } = item
+                {
+                    prefix.push(name);
+                    collect(meta, info, prefix, res);
+                    prefix.pop();
+                }
+            }
+        }
+
+        let mut examples = Vec::new();
+        collect(
+            &self.inner.meta(),
+            &self.info,
+            &mut Vec::new(),
+            &mut examples,
+        );
+        let failures = examples
+            .iter()
+            .filter_map(|(example, words)| {
+                let words = match words {
+                    Ok(words) => words.iter().map(String::as_str).collect::<Vec<_>>(),
+                    Err(err) => return Some(format!("{}: {}", example, err)),
+                };
+                match self.run_inner(words.as_slice()) {
+                    Err(err @ ParseFailure::Stderr(_)) => {
+                        Some(format!("{}: {}", example, err.unwrap_stderr()))
+                    }
+                    Ok(_) | Err(_) => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        if !failures.is_empty() {
+            panic!("Some examples failed to parse:\n{}", failures.join("\n"));
+        }
+    }
+
     /// Set custom usage field
     ///
     /// Custom usage field to use instead of one derived by `bpaf`.
@@ -576,7 +1169,8 @@ impl<T> OptionParser<T> {
         F: Fn(Doc) -> Doc,
     {
         let mut buf = Doc::default();
-        buf.write_meta(&self.inner.meta(), true);
+        buf.slash = self.info.option_style == OptionStyle::Slash;
+        buf.write_usage(&self.inner.meta(), self.info.layout.usage);
         self.info.usage = Some(f(buf));
         self
     }
@@ -647,9 +1241,544 @@ impl<T> OptionParser<T> {
         self.info.help_if_no_args = true;
         self
     }
+
+    /// Accept unique prefixes of long names and command names
+    ///
+    /// By default `bpaf` requires names to match exactly. With this option enabled a prefix
+    /// that identifies exactly one long name or command is accepted as well, similar to GNU
+    /// `getopt_long`: `--verb` for `--verbose` or `bui` for `build`. Exact matches always win
+    /// and a prefix that matches several names produces an error listing all the candidates.
+    ///
+    /// Setting applies to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = long("verbose").switch();
+    /// let version = long("version-info").switch();
+    /// let parser = construct!(verbose, version).to_options().abbreviations();
+    ///
+    /// let r = parser.run_inner(&["--verb"]).unwrap();
+    /// assert_eq!(r, (true, false));
+    ///
+    /// let r = parser.run_inner(&["--ver"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`--ver` is ambiguous, could be `--verbose` or `--version-info`");
+    /// ```
+    #[must_use]
+    pub fn abbreviations(mut self) -> Self {
+        self.info.abbreviations = true;
+        self
+    }
+
+    /// Match long names and command names ignoring the case
+    ///
+    /// By default `bpaf` requires names to match exactly. With this option enabled `--Output-Dir`
+    /// and `--OUTPUT-DIR` are accepted for `--output-dir` and `Build` is accepted for `build`.
+    /// Short names are always case sensitive: `-v` and `-V` are usually different flags.
+    ///
+    /// Setting applies to all the nested subcommands as well and works together with
+    /// [`abbreviations`](OptionParser::abbreviations) and
+    /// [`underscores_as_dashes`](OptionParser::underscores_as_dashes).
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("output-dir")
+    ///     .argument::<String>("DIR")
+    ///     .to_options()
+    ///     .ignore_case();
+    ///
+    /// let r = parser.run_inner(&["--Output-Dir", "tmp"]).unwrap();
+    /// assert_eq!(r, "tmp");
+    /// ```
+    #[must_use]
+    pub fn ignore_case(mut self) -> Self {
+        self.info.ignore_case = true;
+        self
+    }
+
+    /// Treat `_` and `-` in long names and command names as the same character
+    ///
+    /// With this option enabled `--output_dir` is accepted for `--output-dir` and the other way
+    /// around. Setting applies to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let parser = long("output-dir")
+    ///     .argument::<String>("DIR")
+    ///     .to_options()
+    ///     .underscores_as_dashes();
+    ///
+    /// let r = parser.run_inner(&["--output_dir", "tmp"]).unwrap();
+    /// assert_eq!(r, "tmp");
+    /// ```
+    #[must_use]
+    pub fn underscores_as_dashes(mut self) -> Self {
+        self.info.underscores_as_dashes = true;
+        self
+    }
+
+    /// Accept slash style named items: `/f`, `/flag`, `/flag:value` and `/flag=value`
+    ///
+    /// By default `bpaf` accepts only unix style names that start with dashes. With
+    /// [`DashAndSlash`](OptionStyle::DashAndSlash) `bpaf` accepts both styles and with
+    /// [`Slash`](OptionStyle::Slash) - only slash style names, help and usage are
+    /// rendered with slashes as well. Short and long names can both be used with slashes: `/v` or
+    /// `/verbose`, short names can't be combined together. Completion follows the same style.
+    ///
+    /// Only known names are treated as options so positional items that look like unix
+    /// paths such as `/usr/bin` are still parsed as positional items.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').long("verbose").switch();
+    /// let output = long("out").argument::<String>("FILE");
+    /// let parser = construct!(verbose, output)
+    ///     .to_options()
+    ///     .option_style(OptionStyle::Slash);
+    ///
+    /// let r = parser.run_inner(&["/v", "/out:file.txt"]).unwrap();
+    /// assert_eq!(r, (true, "file.txt".to_owned()));
+    ///
+    /// let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "expected `/out:FILE`, pass `/help` for usage information");
+    /// ```
+    #[must_use]
+    pub fn option_style(mut self, style: OptionStyle) -> Self {
+        self.info.option_style = style;
+        self
+    }
+
+    /// Stop parsing options at the first positional item
+    ///
+    /// By default named items can be mixed with positional items in any order. With this option
+    /// enabled everything after the first positional item is treated as positional, similar to
+    /// what `sudo`, `env` or `timeout` do: anything after the wrapped command belongs to it.
+    /// Command names don't stop option parsing so subcommands can still have their own options.
+    ///
+    /// Help message mentions this behavior and completion offers only positional items and
+    /// commands after the first positional item. Setting is used by the top level parser only
+    /// and applies to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let user = short('u').argument::<String>("USER").optional();
+    /// let cmd = positional::<String>("CMD").many();
+    /// let parser = construct!(user, cmd).to_options().options_first();
+    ///
+    /// let r = parser.run_inner(&["-u", "root", "ls", "-u", "--help"]).unwrap();
+    /// assert_eq!(r.0.as_deref(), Some("root"));
+    /// assert_eq!(r.1, ["ls", "-u", "--help"]);
+    /// ```
+    #[must_use]
+    pub fn options_first(mut self) -> Self {
+        self.info.options_first = true;
+        self
+    }
+
+    /// Accept `help` subcommand that prints help for nested subcommands
+    ///
+    /// With this option enabled `app help build deploy` prints the same help as
+    /// `app build deploy --help`, `app help` alone prints help for the app itself. Unknown
+    /// subcommand names produce an error with a suggestion for a possible typo and completion
+    /// offers subcommand names at every level. `help` command is listed with other commands in
+    /// the help message.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let release = long("release").help("Build in release mode").switch();
+    /// let build = construct!(release)
+    ///     .to_options()
+    ///     .descr("Build the project")
+    ///     .command("build");
+    /// let parser = build.to_options().help_command();
+    ///
+    /// let r = parser.run_inner(&["help", "build"]).unwrap_err().unwrap_stdout();
+    /// assert!(r.starts_with("Build the project"));
+    ///
+    /// let r = parser.run_inner(&["help", "biuld"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "no such command or positional: `biuld`, did you mean `build`?");
+    /// ```
+    #[must_use]
+    pub fn help_command(mut self) -> Self {
+        self.info.help_command = true;
+        self
+    }
+
+    /// Search help of the parser and all its subcommands with `--help KEYWORD`
+    ///
+    /// With this option enabled `app --help retry` lists flags, arguments, positional items and
+    /// commands from every nested subcommand that mention `retry` in their name or help,
+    /// grouped by the command path. Matching ignores case and tolerates small typos. If
+    /// [`help_command`](OptionParser::help_command) is enabled as well `app help --search retry`
+    /// does the same.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let retry = long("retry").help("Retry failed downloads").switch();
+    /// let fetch = construct!(retry).to_options().command("fetch");
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let parser = construct!(verbose, fetch).to_options().help_search();
+    ///
+    /// let r = parser.run_inner(&["--help", "retyr"]).unwrap_err().unwrap_stdout();
+    /// assert!(r.contains("--retry"));
+    /// assert!(!r.contains("-v"));
+    /// ```
+    #[must_use]
+    pub fn help_search(mut self) -> Self {
+        self.info.help_search = true;
+        self
+    }
+
+    /// Show help messages that don't fit on the screen in a pager
+    ///
+    /// When [`run`](OptionParser::run) prints help or other message to stdout and stdout is a
+    /// terminal, messages taller than the terminal are piped through the program specified in
+    /// `PAGER` environment variable, `less -FRX` by default, preserving the colors. Terminal size
+    /// is taken from `LINES` and `COLUMNS` if set, otherwise the pager decides if the message
+    /// fits. Short messages, redirected output or a pager that can't be started fall back to
+    /// printing as usual. With [`try_run`](OptionParser::try_run) use
+    /// [`ParseFailure::exit_code_with_pager`] to get the same behavior.
+    ///
+    /// Available with the `pager` feature, which needs Rust 1.70 or newer.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let parser = construct!(verbose).to_options().pager();
+    /// ```
+    #[cfg(feature = "pager")]
+    #[must_use]
+    pub fn pager(mut self) -> Self {
+        self.info.pager = true;
+        self
+    }
+
+    /// Stop parsing options at the first positional item if `POSIXLY_CORRECT` is set
+    ///
+    /// Same as [`options_first`](OptionParser::options_first), but enabled only when
+    /// `POSIXLY_CORRECT` environment variable is present at the time parser runs, similar to GNU
+    /// `getopt`.
+    #[must_use]
+    pub fn posixly_correct(mut self) -> Self {
+        self.info.posixly_correct = true;
+        self
+    }
+
+    /// Order of items within each help section
+    ///
+    /// By default items are listed in the order they are declared in the parser,
+    /// [`HelpOrder::Alphabetical`] sorts them by name within every section, group and heading.
+    /// Setting is used by the top level parser only and applies to all the nested subcommands
+    /// as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').long("verbose").help("Verbose output").switch();
+    /// let color = long("color").help("Use colors").switch();
+    /// let parser = construct!(verbose, color)
+    ///     .to_options()
+    ///     .help_order(HelpOrder::Alphabetical);
+    ///
+    /// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// let expected = "\
+    /// Usage: [-v] [--color]
+    ///
+    /// Available options:
+    ///         --color    Use colors
+    ///     -h, --help     Prints help information
+    ///     -v, --verbose  Verbose output
+    /// ";
+    /// assert_eq!(r, expected);
+    /// ```
+    #[must_use]
+    pub fn help_order(mut self, order: HelpOrder) -> Self {
+        self.info.layout.order = order;
+        self
+    }
+
+    /// Change a title of one of the standard help sections
+    ///
+    /// Setting is used by the top level parser only and applies to all the nested subcommands
+    /// as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let parser = construct!(verbose)
+    ///     .to_options()
+    ///     .section_title(HelpSection::Options, "Flags:");
+    ///
+    /// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// assert!(r.contains("\nFlags:\n"));
+    /// ```
+    #[must_use]
+    pub fn section_title(mut self, section: HelpSection, title: &'static str) -> Self {
+        let layout = &mut self.info.layout;
+        match section {
+            HelpSection::Positionals => layout.positionals_title = title,
+            HelpSection::Options => layout.options_title = title,
+            HelpSection::Commands => layout.commands_title = title,
+        }
+        self
+    }
+
+    /// Placement of help next to item names
+    ///
+    /// Help is aligned in a column next to item names, names that don't fit into the column
+    /// are followed by help on the same line by default. With [`HelpColumns::TwoLine`] help for
+    /// such names starts on the next line instead. Setting is used by the top level parser only
+    /// and applies to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let retries = long("maximum-number-of-retries")
+    ///     .help("Give up after that many attempts")
+    ///     .argument::<usize>("N");
+    /// let parser = construct!(verbose, retries)
+    ///     .to_options()
+    ///     .help_columns(HelpColumns::TwoLine);
+    ///
+    /// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// let expected = "\
+    /// Usage: [-v] --maximum-number-of-retries=N
+    ///
+    /// Available options:
+    ///     -v          Verbose output
+    ///         --maximum-number-of-retries=N
+    ///                 Give up after that many attempts
+    ///     -h, --help  Prints help information
+    /// ";
+    /// assert_eq!(r, expected);
+    /// ```
+    #[must_use]
+    pub fn help_columns(mut self, columns: HelpColumns) -> Self {
+        self.info.layout.columns = columns;
+        self
+    }
+
+    /// Rendering of the generated usage line
+    ///
+    /// By default usage lists every item the parser accepts which gets long for parsers with
+    /// many optional flags. With [`UsageStyle::Compact`] optional named items are collapsed into
+    /// a single `[OPTIONS]`, required items, positional items and alternatives stay explicit,
+    /// subcommands are always shown as `COMMAND ...`. Style applies to usage in help messages,
+    /// generated markdown and manpages. Setting is used by the top level parser only and applies
+    /// to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let jobs = short('j').help("Number of jobs").argument::<usize>("N").optional();
+    /// let target = long("target").help("Build target").argument::<String>("TARGET");
+    /// let file = positional::<String>("FILE");
+    /// let parser = construct!(verbose, jobs, target, file)
+    ///     .to_options()
+    ///     .usage_style(UsageStyle::Compact);
+    ///
+    /// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// assert!(r.starts_with("Usage: [OPTIONS] --target=TARGET FILE\n"));
+    /// ```
+    #[must_use]
+    pub fn usage_style(mut self, style: UsageStyle) -> Self {
+        self.info.layout.usage = style;
+        self
+    }
+
+    /// Presentation of parse errors
+    ///
+    /// By default errors are reported with a single sentence. [`ErrorStyle::Diagnostic`] also
+    /// reprints the command line with offending items underlined, shows usage of the failing
+    /// (sub)command and moves suggestions for a fix into a separate note. Setting is used by the
+    /// top level parser only and applies to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').long("verbose").switch();
+    /// let jobs = long("jobs").argument::<usize>("N");
+    /// let parser = construct!(verbose, jobs)
+    ///     .to_options()
+    ///     .error_style(ErrorStyle::Diagnostic);
+    ///
+    /// let r = parser.run_inner(&["-v", "--jobs", "many"]).unwrap_err().unwrap_stderr();
+    /// let expected = "\
+    /// couldn't parse `many`: invalid digit found in string
+    ///
+    ///     -v --jobs many
+    ///               ^^^^
+    ///
+    /// usage: [-v] --jobs=N";
+    /// assert_eq!(r, expected);
+    ///
+    /// let r = parser.run_inner(&["--verbos", "--jobs", "2"]).unwrap_err().unwrap_stderr();
+    /// let expected = "\
+    /// no such flag: `--verbos`
+    ///
+    ///     --verbos --jobs 2
+    ///     ^^^^^^^^
+    ///
+    /// help: did you mean `--verbose`?
+    ///
+    /// usage: [-v] --jobs=N";
+    /// assert_eq!(r, expected);
+    /// ```
+    #[must_use]
+    pub fn error_style(mut self, style: ErrorStyle) -> Self {
+        self.info.error_style = style;
+        self
+    }
+
+    /// Report up to `max` problems with the command line at once
+    ///
+    /// By default parsing stops at the first failure. With a bigger limit fields of
+    /// [`construct!`](crate::construct!) keep being parsed after one of them fails and
+    /// unexpected items left on the command line are reported as well, so users can fix several
+    /// mistakes in one go. Only independent failures are collected: if an outer parser can handle
+    /// the first failure with [`optional`](Parser::optional), [`fallback`](Parser::fallback) or
+    /// an alternative branch, the rest are dropped along with it. Problems found by a failed
+    /// subcommand are listed along with the problems of the parent parser, problems past the
+    /// limit are counted but not shown. Setting is used by the top level parser only and applies
+    /// to all the nested subcommands as well.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').long("verbose").switch();
+    /// let jobs = long("jobs").argument::<usize>("N");
+    /// let name = long("name").argument::<String>("NAME");
+    /// let parser = construct!(verbose, jobs, name).to_options().max_errors(5);
+    ///
+    /// let r = parser
+    ///     .run_inner(&["--jobs", "many", "--verbos"])
+    ///     .unwrap_err()
+    ///     .unwrap_stderr();
+    /// let expected = "\
+    /// 3 problems found:
+    ///   - couldn't parse `many`: invalid digit found in string
+    ///   - expected `--name=NAME`, pass `--help` for usage information
+    ///   - no such flag: `--verbos`, did you mean `--verbose`?
+    /// ";
+    /// assert_eq!(r, expected);
+    /// ```
+    #[must_use]
+    pub fn max_errors(mut self, max: usize) -> Self {
+        self.info.max_errors = max.max(1);
+        self
+    }
+
+    /// Check environment variables with this prefix for typos in names
+    ///
+    /// Environment variables are easy to misspell and a misspelled one is silently ignored.
+    /// With a prefix set [`run`](OptionParser::run) warns about variables that start with it,
+    /// are not used by any of the parsers including subcommands and look like a typo in one of
+    /// the names declared with [`env`](NamedArg::env), see
+    /// [`env_warnings`](OptionParser::env_warnings). Checks don't depend on cargo features,
+    /// unlike suggestions for invalid values which need `autocomplete`, see
+    /// [`complete`](Parser::complete).
+    #[must_use]
+    pub fn env_prefix(mut self, prefix: &'static str) -> Self {
+        self.info.env_prefix = Some(prefix);
+        self
+    }
+
+    /// Print environment variable warnings to stderr in [`run`](OptionParser::run)
+    ///
+    /// Enabled by default, warnings are printed only after a successful parse. Disable it to
+    /// report them some other way with [`env_warnings`](OptionParser::env_warnings) or not at
+    /// all.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let jobs = long("jobs").env("APP_JOBS").argument::<usize>("N").fallback(1);
+    /// let parser = jobs
+    ///     .to_options()
+    ///     .env_prefix("APP_")
+    ///     .print_env_warnings(false);
+    /// ```
+    #[must_use]
+    pub fn print_env_warnings(mut self, print: bool) -> Self {
+        self.info.print_env_warnings = print;
+        self
+    }
+}
+
+/// Split a command line into words the way a POSIX shell does it, without expansions
+fn split_shell_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    // current word, `None` between words
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(String::from("unterminated single quote")),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(String::from("unterminated double quote")),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(String::from("unterminated double quote")),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => return Err(String::from("trailing backslash")),
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
 }
 
 impl Info {
+    /// Help and version items along with global items from the parent parsers, used for help
+    pub(crate) fn help_meta(&self, globals: &[Item]) -> Meta {
+        let meta = self.meta();
+        if globals.is_empty() {
+            return meta;
+        }
+        let globals = Meta::And(globals.iter().cloned().map(Meta::from).collect());
+        let mut header = Doc::default();
+        header.emphasis("Global options:");
+        Meta::And(vec![
+            meta,
+            Meta::Subsection(Box::new(globals), Box::new(header)),
+        ])
+    }
+
+    /// Names handled by the parser itself: help and version, if present
+    fn reserved(&self) -> std::rc::Rc<[NamedArg]> {
+        let mut res = vec![self.help_arg.clone()];
+        if self.version.is_some() {
+            res.push(self.version_arg.clone());
+        }
+        res.into()
+    }
+
+    fn help_command_help() -> Doc {
+        Doc::from("Prints help for a subcommand")
+    }
+
     #[inline(never)]
     fn mk_help_parser(&self) -> impl Parser<()> {
         self.help_arg.clone().req_flag(())
@@ -664,6 +1793,12 @@ impl Parser<ExtraParams> for Info {
     fn eval(&self, args: &mut State) -> Result<ExtraParams, Error> {
         let help = self.mk_help_parser();
         if help.eval(args).is_ok() {
+            if self.help_search {
+                // `--help KEYWORD`, help flag is the last consumed item
+                if let Some(Arg::Word(w)) = args.current.and_then(|ix| args.items.get(ix + 1)) {
+                    return Ok(ExtraParams::Search(w.to_string_lossy().into_owned()));
+                }
+            }
             return Ok(ExtraParams::Help(help.eval(args).is_ok()));
         }
 
@@ -673,15 +1808,48 @@ impl Parser<ExtraParams> for Info {
             }
         }
 
+        if self.help_command && args.take_cmd("help") {
+            if self.help_search {
+                // `help --search KEYWORD`
+                let mut items = args.items_iter();
+                if let (Some((_, Arg::Long(name, false, _))), Some((_, Arg::Word(w)))) =
+                    (items.next(), items.next())
+                {
+                    if name == "search" {
+                        return Ok(ExtraParams::Search(w.to_string_lossy().into_owned()));
+                    }
+                }
+            }
+            let words = args
+                .items_iter()
+                .take_while(|(_, arg)| matches!(arg, Arg::Word(_)))
+                .map(|(ix, _)| ix)
+                .collect();
+            return Ok(ExtraParams::HelpCommand(words));
+        }
+
         // error message is not actually used anywhere
         Err(Error(Message::ParseFail("not a version or help")))
     }
 
     fn meta(&self) -> Meta {
-        let help = self.mk_help_parser().meta();
-        match &self.version {
-            Some(_) => Meta::And(vec![help, self.mk_version_parser().meta()]),
-            None => help,
+        let mut res = vec![self.mk_help_parser().meta()];
+        if self.version.is_some() {
+            res.push(self.mk_version_parser().meta());
+        }
+        if self.help_command {
+            res.push(Meta::from(Item::Command {
+                name: "help",
+                short: None,
+                help: Some(Self::help_command_help()),
+                meta: Box::new(Meta::Skip),
+                info: Box::default(),
+            }));
+        }
+        if res.len() == 1 {
+            res.remove(0)
+        } else {
+            Meta::And(res)
         }
     }
 }
@@ -690,4 +1858,8 @@ impl Parser<ExtraParams> for Info {
 pub(crate) enum ExtraParams {
     Help(bool),
     Version(Doc),
+    /// `help` subcommand with indices of subcommand names that follow it
+    HelpCommand(Vec<usize>),
+    /// `--help KEYWORD` or `help --search KEYWORD`
+    Search(String),
 }
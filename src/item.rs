@@ -26,8 +26,14 @@ pub enum Item {
         name: ShortLong,
         /// used for disambiguation
         shorts: Vec<char>,
+        /// all the long names including hidden aliases, used for splitting
+        longs: Vec<&'static str>,
+        /// single dash long names, used for splitting
+        singles: Vec<&'static str>,
         env: Option<&'static str>,
         help: Option<Doc>,
+        /// available in all the nested subcommands
+        global: bool,
     },
     /// Short or long name followed by a value, consumed anywhere
     /// -f <VAL>
@@ -36,9 +42,15 @@ pub enum Item {
         name: ShortLong,
         /// used for disambiguation
         shorts: Vec<char>,
+        /// all the long names including hidden aliases, used for splitting
+        longs: Vec<&'static str>,
+        /// single dash long names, used for splitting
+        singles: Vec<&'static str>,
         metavar: Metavar,
         env: Option<&'static str>,
         help: Option<Doc>,
+        /// available in all the nested subcommands
+        global: bool,
     },
 }
 
@@ -61,23 +73,26 @@ impl Item {
 
 #[doc(hidden)]
 #[derive(Copy, Clone, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum ShortLong {
     Short(char),
     Long(&'static str),
     ShortLong(char, &'static str),
+    /// Single dash long name: `-name`, `true` if it also accepts `+name` as a toggle
+    Single(&'static str, bool),
 }
 
 impl ShortLong {
     pub(crate) fn as_long(&self) -> Option<&'static str> {
         match self {
             ShortLong::Long(l) | ShortLong::ShortLong(_, l) => Some(l),
-            ShortLong::Short(_) => None,
+            ShortLong::Short(_) | ShortLong::Single(_, _) => None,
         }
     }
     pub(crate) fn as_short(&self) -> Option<char> {
         match self {
             ShortLong::Short(s) | ShortLong::ShortLong(s, _) => Some(*s),
-            ShortLong::Long(_) => None,
+            ShortLong::Long(_) | ShortLong::Single(_, _) => None,
         }
     }
 }
@@ -95,6 +110,10 @@ impl PartialEq<&str> for ShortLong {
             ShortLong::Short(s) => short_eq(*s, other),
             ShortLong::Long(l) => long_eq(l, other),
             ShortLong::ShortLong(s, l) => short_eq(*s, other) || long_eq(l, other),
+            ShortLong::Single(l, toggle) => {
+                other.strip_prefix('-') == Some(l)
+                    || (*toggle && other.strip_prefix('+') == Some(l))
+            }
         }
     }
 }
@@ -104,7 +123,7 @@ impl ShortLong {
     /// leaves both Short and Long untouched
     pub(crate) fn normalize(&mut self, short: bool) {
         match self {
-            ShortLong::Short(_) | ShortLong::Long(_) => {}
+            ShortLong::Short(_) | ShortLong::Long(_) | ShortLong::Single(_, _) => {}
             ShortLong::ShortLong(s, l) => {
                 if short {
                     *self = Self::Short(*s);
@@ -121,7 +140,10 @@ impl TryFrom<&NamedArg> for ShortLong {
 
     fn try_from(named: &NamedArg) -> Result<Self, Self::Error> {
         match (named.short.is_empty(), named.long.is_empty()) {
-            (true, true) => Err(()),
+            (true, true) => match named.single.first() {
+                Some(name) => Ok(Self::Single(name, false)),
+                None => Err(()),
+            },
             (true, false) => Ok(Self::Long(named.long[0])),
             (false, true) => Ok(Self::Short(named.short[0])),
             (false, false) => Ok(Self::ShortLong(named.short[0], named.long[0])),
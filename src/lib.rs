@@ -101,13 +101,22 @@
 //! ## Improving user experience
 //!
 //! `bpaf` would use doc comments on fields and structures in derive mode and and values passed
-//! in various `help` methods to generate `--help` documentation, you can further improve it
+//! in various `help` methods to generate `--help` documentation. Doc comments can use a subset of
+//! markdown: `` `--literal` ``, `*emphasis*`, `<METAVAR>`, bullet and numbered lists and fenced
+//! code blocks are rendered with [`Doc`](doc::Doc) styles. You can further improve it
 //! using those methods:
 //!
 //! - [`hide_usage`](Parser::hide_usage) and [`hide`](Parser::hide) - hide the parser from
 //!   generated *Usage* line or whole generated help
 //! - [`group_help`](Parser::group_help) and [`with_group_help`](Parser::with_group_help) -
 //!   add a common description shared by several parsers
+//! - [`heading`](Parser::heading) - list items under a custom heading regardless of the parser
+//!   structure
+//! - [`help_order`](OptionParser::help_order), [`section_title`](OptionParser::section_title) and
+//!   [`help_columns`](OptionParser::help_columns) - sort items, rename standard sections and
+//!   move help for long names to the next line
+//! - [`usage_style`](OptionParser::usage_style) - collapse optional named items in the usage line
+//!   into `[OPTIONS]`
 //! - [`custom_usage`](Parser::custom_usage) - customize usage for a primitive or composite parser
 //! - [`usage`](OptionParser::usage) and [`with_usage`](OptionParser::with_usage) lets you to
 //!   customize whole usage line as a whole either by completely overriding it or by building around it.
@@ -162,6 +171,7 @@
 #[cfg(feature = "extradocs")]
 #[rustfmt::skip]
 #[allow(unused_imports)]
+#[allow(clippy::tabs_in_doc_comments, clippy::doc_overindented_list_items)]
 pub mod _documentation;
 
 mod arg;
@@ -175,6 +185,7 @@ mod complete_gen;
 mod complete_run;
 #[cfg(feature = "autocomplete")]
 mod complete_shell;
+#[allow(clippy::test_attr_in_doctest)]
 pub mod doc;
 mod error;
 mod from_os_str;
@@ -183,6 +194,8 @@ mod item;
 mod meta;
 mod meta_help;
 mod meta_youmean;
+#[cfg(feature = "pager")]
+mod pager;
 pub mod params;
 mod structs;
 #[cfg(test)]
@@ -198,7 +211,8 @@ pub mod parsers {
     pub use crate::complete_shell::ParseCompShell;
     #[doc(inline)]
     pub use crate::params::{
-        NamedArg, ParseAny, ParseArgument, ParseCommand, ParseFlag, ParsePositional,
+        NamedArg, ParseAny, ParseArgument, ParseCommand, ParseFlag, ParsePassThrough,
+        ParsePositional,
     };
     #[doc(inline)]
     pub use crate::structs::{
@@ -210,7 +224,14 @@ pub mod parsers {
 // -------------------------------------------------------------------
 
 #[doc(inline)]
-pub use crate::{args::Args, buffer::Doc, error::ParseFailure, info::OptionParser};
+pub use crate::{
+    args::Args,
+    buffer::Doc,
+    error::ParseFailure,
+    info::{
+        ErrorStyle, HelpColumns, HelpOrder, HelpSection, OptionParser, OptionStyle, UsageStyle,
+    },
+};
 
 #[doc(hidden)]
 // used by construct macro, not part of public API
@@ -222,11 +243,12 @@ use crate::{
     buffer::{MetaInfo, Style},
     item::Item,
     params::build_positional,
-    parsers::{NamedArg, ParseAny, ParseCommand, ParsePositional},
+    parsers::{NamedArg, ParseAny, ParseCommand, ParsePassThrough, ParsePositional},
     structs::{
         ParseCollect, ParseCount, ParseFail, ParseFallback, ParseFallbackWith, ParseGroupHelp,
-        ParseGuard, ParseHide, ParseLast, ParseMany, ParseMap, ParseOptional, ParseOrElse,
-        ParsePure, ParsePureWith, ParseSome, ParseUsage, ParseWith, ParseWithGroupHelp,
+        ParseGuard, ParseGuardWith, ParseHeading, ParseHide, ParseLast, ParseMany, ParseMap,
+        ParseOptional, ParseOrElse, ParsePure, ParsePureWith, ParseSome, ParseStyled, ParseUsage,
+        ParseWith, ParseWithGroupHelp,
     },
 };
 
@@ -399,7 +421,6 @@ pub use bpaf_derive::Bpaf;
 ///     CD { c: u32, d: u32 }
 /// }
 /// ```
-
 #[macro_export]
 macro_rules! construct {
     // construct!(Enum::Cons { a, b, c })
@@ -442,34 +463,6 @@ macro_rules! construct {
 
 #[macro_export]
 #[doc(hidden)]
-#[cfg(not(feature = "autocomplete"))]
-/// to avoid extra parsing when autocomplete feature is off
-macro_rules! __cons_prepare {
-    ([named [$($con:tt)+]] []) => { $crate::pure($($con)+ { })};
-    ([pos   [$($con:tt)+]] []) => { $crate::pure($($con)+ ( ))};
-
-    ([pos] [$field:ident]) => {{
-        use $crate::Parser;
-        $field.boxed()
-    }};
-
-    ($ty:tt [$($fields:ident)+]) => {{
-        use $crate::Parser;
-        let meta = $crate::Meta::And(vec![ $($fields.meta()),+ ]);
-        let inner = move |args: &mut $crate::State| {
-            $(let $fields = $fields.eval(args)?;)+
-            args.current = None;
-            ::std::result::Result::Ok::<_, $crate::Error>
-                ($crate::construct!(@make $ty [$($fields)+]))
-        };
-        $crate::ParseCon { inner, meta }
-    }};
-}
-
-#[macro_export]
-#[doc(hidden)]
-#[cfg(feature = "autocomplete")]
-/// for completion bpaf needs to observe all the failures in a branch
 macro_rules! __cons_prepare {
     ([named [$($con:tt)+]] []) => { $crate::pure($($con)+ { })};
     ([pos   [$($con:tt)+]] []) => { $crate::pure($($con)+ ( ))};
@@ -483,13 +476,13 @@ macro_rules! __cons_prepare {
         use $crate::Parser;
         let meta = $crate::Meta::And(vec![ $($fields.meta()),+ ]);
         let inner = move |args: &mut $crate::State| {
-            $(let $fields = if args.is_comp() {
-                $fields.eval(args)
-            } else {
-                Ok($fields.eval(args)?)
-            };)+
+            // fields can keep parsing after a failure, see `State::collect_error`
+            let mut failed = false;
+            $(
+                let $fields = $fields.eval(args);
+                let $fields = args.collect_error($fields, &mut failed)?;
+            )+
             $(let $fields = $fields?;)+
-
             args.current = None;
             ::std::result::Result::Ok::<_, $crate::Error>
                 ($crate::construct!(@make $ty [$($fields)+]))
@@ -658,6 +651,8 @@ pub trait Parser<T> {
         ParseMany {
             inner: self,
             catch: false,
+            min: 0,
+            max: usize::MAX,
         }
     }
     // }}}
@@ -685,6 +680,8 @@ pub trait Parser<T> {
         ParseCollect {
             inner: self,
             catch: false,
+            min: 0,
+            max: usize::MAX,
             ctx: PhantomData,
         }
     }
@@ -709,7 +706,6 @@ pub trait Parser<T> {
     /// [`many`](Parser::many) also collects results to a vector but succeeds with
     /// no matching values. [`collect`](Parser::collect) collects results into a [`FromIterator`]
     /// structure
-
     #[must_use]
     fn some(self, message: &'static str) -> ParseSome<Self>
     where
@@ -819,6 +815,49 @@ pub trait Parser<T> {
             err: PhantomData,
         }
     }
+
+    /// Apply a failing transformation to a contained value, fail with a styled message
+    ///
+    /// Works the same way as [`parse`](Parser::parse), but error can be anything that converts
+    /// into a [`Doc`]: a string, a list of styled fragments or a [`Doc`] itself. Unlike
+    /// `parse` that flattens the error into a plain string, fragments keep their styles when
+    /// the error is rendered with colors or as markdown.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// use bpaf::doc::{Doc, Style};
+    /// fn port(input: String) -> Result<u16, Doc> {
+    ///     match input.parse::<u16>() {
+    ///         Ok(port) if port >= 1024 => Ok(port),
+    ///         _ => {
+    ///             let mut doc = Doc::default();
+    ///             doc.text("expected a number between ");
+    ///             doc.literal("1024");
+    ///             doc.text(" and ");
+    ///             doc.literal("65535");
+    ///             Err(doc)
+    ///         }
+    ///     }
+    /// }
+    /// let parser = long("port").argument::<String>("PORT").parse_styled(port).to_options();
+    ///
+    /// let r = parser.run_inner(&["--port", "80"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "couldn't parse `80`: expected a number between 1024 and 65535");
+    /// ```
+    fn parse_styled<F, R, E>(self, f: F) -> ParseStyled<T, Self, F, E, R>
+    where
+        Self: Sized + Parser<T>,
+        F: Fn(T) -> Result<R, E>,
+        E: Into<Doc>,
+    {
+        ParseStyled {
+            inner: self,
+            inner_res: PhantomData,
+            parse_fn: f,
+            res: PhantomData,
+            err: PhantomData,
+        }
+    }
     // }}}
 
     // {{{ map
@@ -869,6 +908,37 @@ pub trait Parser<T> {
             message,
         }
     }
+
+    /// Validate or fail with a message computed from the value
+    ///
+    /// Works the same way as [`guard`](Parser::guard), but the message is produced by a
+    /// function from the rejected value. The function can return anything that converts into
+    /// a [`Doc`] so the message can mention the value and use styles.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let jobs = long("jobs")
+    ///     .argument::<usize>("N")
+    ///     .guard_with(|n| *n <= 64, |n| format!("{} is too many, 64 at most", n));
+    /// let parser = jobs.to_options();
+    ///
+    /// let r = parser.run_inner(&["--jobs", "100"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "`100`: 100 is too many, 64 at most");
+    /// ```
+    #[must_use]
+    fn guard_with<F, M, D>(self, check: F, message: M) -> ParseGuardWith<Self, F, M>
+    where
+        Self: Sized + Parser<T>,
+        F: Fn(&T) -> bool,
+        M: Fn(&T) -> D,
+        D: Into<Doc>,
+    {
+        ParseGuardWith {
+            inner: self,
+            check,
+            message,
+        }
+    }
     // }}}
 
     // combine
@@ -1095,6 +1165,51 @@ pub trait Parser<T> {
         ParseWithGroupHelp { inner: self, f }
     }
 
+    /// List items of this parser in help under a custom heading
+    ///
+    /// Unlike [`group_help`](Parser::group_help) heading doesn't depend on the parser structure:
+    /// all the items with the same heading are listed together in a separate section no matter
+    /// where they are declared, including items inside of groups. Items can be of any kind:
+    /// positional items, options and commands. Groups inside of a heading keep their titles
+    /// and are listed after the other items of the heading.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let verbose = short('v').help("Verbose output").switch();
+    /// let host = long("host")
+    ///     .help("Server to connect to")
+    ///     .argument::<String>("HOST")
+    ///     .heading("Network:");
+    /// let port = long("port")
+    ///     .help("Port to use")
+    ///     .argument::<u16>("PORT")
+    ///     .heading("Network:");
+    /// let parser = construct!(host, verbose, port).to_options();
+    ///
+    /// let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    /// let expected = "\
+    /// Usage: --host=HOST [-v] --port=PORT
+    ///
+    /// Network:
+    ///         --host=HOST  Server to connect to
+    ///         --port=PORT  Port to use
+    ///
+    /// Available options:
+    ///     -v               Verbose output
+    ///     -h, --help       Prints help information
+    /// ";
+    /// assert_eq!(r, expected);
+    /// ```
+    fn heading(self, heading: &'static str) -> ParseHeading<Self>
+    where
+        Self: Sized + Parser<T>,
+    {
+        ParseHeading {
+            inner: self,
+            heading,
+        }
+    }
+
     // {{{ comp
     /// Dynamic shell completion
     ///
@@ -1107,6 +1222,14 @@ pub trait Parser<T> {
     /// string if a value isn't available yet so it's best to run `complete` where parsing can't fail:
     /// right after [`argument`](NamedArg::argument) or [`positional`], but this isn't enforced.
     ///
+    /// When a value fails to [`parse`](Parser::parse) or pass a [`guard`](Parser::guard), the
+    /// error message suggests the closest candidate `complete` produces for an empty input. To
+    /// collect the candidates `bpaf` runs the whole parser once more with the failing value
+    /// replaced by an empty string, so closures given to [`map`](Parser::map),
+    /// [`parse`](Parser::parse), [`guard`](Parser::guard) and `complete` run twice for such
+    /// command lines and should not have side effects. Same as `complete` itself suggestions are
+    /// available only with `autocomplete` feature enabled.
+    ///
     /// # Example
     /// ```console
     /// $ app --name L<TAB>
@@ -1238,8 +1361,6 @@ pub trait Parser<T> {
 
     /// Create a boxed representation for a parser
     ///
-    ///
-
     /// The boxed parser doesn't expose internal representation in its type and allows to return
     /// of different parsers in different conditional branches
     ///
@@ -1332,7 +1453,9 @@ pub fn short(short: char) -> NamedArg {
         short: vec![short],
         env: Vec::new(),
         long: Vec::new(),
+        single: Vec::new(),
         help: None,
+        global: false,
     }
 }
 
@@ -1348,8 +1471,10 @@ pub fn long(long: &'static str) -> NamedArg {
     NamedArg {
         short: Vec::new(),
         long: vec![long],
+        single: Vec::new(),
         env: Vec::new(),
         help: None,
+        global: false,
     }
 }
 
@@ -1377,8 +1502,45 @@ pub fn env(variable: &'static str) -> NamedArg {
     NamedArg {
         short: Vec::new(),
         long: Vec::new(),
+        single: Vec::new(),
         help: None,
         env: vec![variable],
+        global: false,
+    }
+}
+
+/// Parse a [`flag`](NamedArg::flag)/[`switch`](NamedArg::switch)/[`argument`](NamedArg::argument)/[`toggle`](NamedArg::toggle)
+/// that has a long name with a single dash: `-name`
+///
+/// Some programs such as `find` or `xorg` use long names that start with a single dash, these
+/// names take priority over groups of short flags: with `-name` defined `bpaf` would parse
+/// `-name` as a single item even if `-n`, `-a`, `-m` and `-e` are also valid short flags.
+/// Single dash names can't have an adjacent value: `-name foo` works, `-name=foo` doesn't.
+///
+/// You can chain multiple [`single_dash`](NamedArg::single_dash) names, `bpaf` would use items
+/// past the first one as hidden aliases. If a short or a long name is also present - single dash
+/// names are only used as hidden aliases.
+///
+/// ```rust
+/// # use bpaf::*;
+/// let name = single_dash("name")
+///     .help("Base of file name matches shell pattern")
+///     .argument::<String>("PATTERN");
+/// let empty = single_dash("empty").help("File is empty").switch();
+/// let parser = construct!(name, empty).to_options();
+///
+/// let r = parser.run_inner(&["-empty", "-name", "*.rs"]).unwrap();
+/// assert_eq!(r, ("*.rs".to_owned(), true));
+/// ```
+#[must_use]
+pub fn single_dash(name: &'static str) -> NamedArg {
+    NamedArg {
+        short: Vec::new(),
+        long: Vec::new(),
+        single: vec![name],
+        env: Vec::new(),
+        help: None,
+        global: false,
     }
 }
 
@@ -1439,7 +1601,7 @@ where
     ParseCommand {
         longs: vec![name],
         shorts: Vec::new(),
-        help: subparser.short_descr().map(Into::into),
+        help: subparser.short_descr(),
         subparser,
         adjacent: false,
     }
@@ -1520,6 +1682,35 @@ pub fn literal(val: &'static str) -> ParseAny<()> {
         .metavar(&[(val, crate::buffer::Style::Literal)][..])
 }
 
+/// Collect all the items other parsers didn't consume instead of failing
+///
+/// Wrappers often need to forward unknown options to the tool they wrap. `pass_through`
+/// collects all the remaining unconsumed items in the order they were given, keeping `--key=val`
+/// and `-kval` forms intact. Since it looks at items other parsers left behind it should be the
+/// last item in [`construct!`]. Parser never fails, if there's nothing to collect it produces an
+/// empty vector.
+///
+/// By default unknown items can be mixed with known ones in any order, use
+/// [`before_known`](ParsePassThrough::before_known) or
+/// [`after_known`](ParsePassThrough::after_known) to restrict that. Usage line renders it as
+/// `[ARGS passed through]`.
+///
+/// ```rust
+/// # use bpaf::*;
+/// # use std::ffi::OsString;
+/// let release = long("release").switch();
+/// let rest = pass_through();
+/// let parser = construct!(release, rest).to_options();
+///
+/// let r = parser.run_inner(&["-O2", "--release", "--target=x86", "-Wall"]).unwrap();
+/// assert!(r.0);
+/// assert_eq!(r.1, ["-O2", "--target=x86", "-Wall"].map(OsString::from));
+/// ```
+#[must_use]
+pub fn pass_through() -> ParsePassThrough {
+    ParsePassThrough::new()
+}
+
 /// Strip a command name if present at the front when used as a `cargo` command
 ///
 // this is exactly the same as batteries::cargo_helper, but used by derive macro...
@@ -1,4 +1,8 @@
-use crate::{buffer::Doc, item::Item};
+use crate::{
+    args::{CommandNames, KnownNames, NameMatch},
+    buffer::{Doc, Style},
+    item::{Item, ShortLong},
+};
 
 #[doc(hidden)]
 #[derive(Clone, Debug)]
@@ -17,12 +21,17 @@ pub enum Meta {
     Item(Box<Item>),
     /// Accepts multiple arguments
     Many(Box<Meta>),
+    /// Accepts between `min` and `max` arguments, `max` is `usize::MAX` when unbounded
+    Bounded(Box<Meta>, usize, usize),
     /// Arguments form a subsection with buffer being it's header
     ///
     /// whole set of arguments go into the same section as the first one
     Subsection(Box<Meta>, Box<Doc>),
     /// Buffer is rendered after
     Suffix(Box<Meta>, Box<Doc>),
+    /// Arguments are listed in help under a custom heading along with all the other
+    /// arguments with the same heading
+    Heading(Box<Meta>, &'static str),
     /// This item is not rendered in the help message
     Skip,
     /// TODO make it Option<Box<Doc>>
@@ -66,7 +75,7 @@ impl Meta {
     fn is_command(&self) -> bool {
         match self {
             Meta::Item(i) => matches!(i.as_ref(), Item::Command { .. }),
-            Meta::Subsection(m, _) => m.is_command(),
+            Meta::Subsection(m, _) | Meta::Heading(m, _) => m.is_command(),
             _ => false,
         }
     }
@@ -122,8 +131,10 @@ impl Meta {
                 Meta::Optional(m)
                 | Meta::Required(m)
                 | Meta::Many(m)
+                | Meta::Bounded(m, _, _)
                 | Meta::CustomUsage(m, _)
                 | Meta::Subsection(m, _)
+                | Meta::Heading(m, _)
                 | Meta::Strict(m)
                 | Meta::Suffix(m, _) => go(m, is_pos, v),
                 Meta::Skip => {}
@@ -164,7 +175,9 @@ impl Meta {
             | Meta::Required(x)
             | Meta::Adjacent(x)
             | Meta::Many(x)
+            | Meta::Bounded(x, _, _)
             | Meta::Subsection(x, _)
+            | Meta::Heading(x, _)
             | Meta::Suffix(x, _)
             | Meta::CustomUsage(x, _) => Self::first_item(x),
         }
@@ -260,13 +273,16 @@ impl Meta {
                     *self = std::mem::take(m);
                 }
             }
-            Meta::Many(m) => {
+            Meta::Many(m) | Meta::Bounded(m, _, _) => {
                 m.normalize(for_usage, norm);
                 if matches!(**m, Meta::Skip) {
                     *self = Meta::Skip;
                 }
             }
-            Meta::Adjacent(m) | Meta::Subsection(m, _) | Meta::Suffix(m, _) => {
+            Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Heading(m, _)
+            | Meta::Suffix(m, _) => {
                 m.normalize(for_usage, norm);
                 *self = std::mem::take(m);
             }
@@ -294,6 +310,64 @@ impl Meta {
     }
 }
 
+impl Meta {
+    /// Replace optional named items with a single `[OPTIONS]`, meta must be normalized
+    ///
+    /// Only items that are always optional are collapsed, required items, positional items,
+    /// commands and alternatives stay explicit
+    pub(crate) fn collapse_options(&mut self) {
+        fn go(meta: &mut Meta, collapsed: &mut bool) {
+            match meta {
+                Meta::And(xs) => {
+                    for x in xs.iter_mut() {
+                        go(x, collapsed);
+                    }
+                    xs.retain(|x| !matches!(x, Meta::Skip));
+                }
+                m if m.is_optional_named() => {
+                    *m = if std::mem::replace(collapsed, true) {
+                        Meta::Skip
+                    } else {
+                        let mut options = Doc::default();
+                        options.text("[");
+                        options.write_str("OPTIONS", Style::Metavar);
+                        options.text("]");
+                        Meta::CustomUsage(Box::new(Meta::Skip), Box::new(options))
+                    };
+                }
+                _ => {}
+            }
+        }
+        go(self, &mut false);
+    }
+
+    fn is_optional_named(&self) -> bool {
+        match self {
+            Meta::Optional(m) | Meta::Bounded(m, 0, _) => m.is_named(),
+            Meta::Many(m) | Meta::Bounded(m, _, _) => m.is_optional_named(),
+            _ => false,
+        }
+    }
+
+    /// Contains only named items
+    fn is_named(&self) -> bool {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => xs.iter().all(Meta::is_named),
+            Meta::Optional(m)
+            | Meta::Required(m)
+            | Meta::Adjacent(m)
+            | Meta::Many(m)
+            | Meta::Bounded(m, _, _)
+            | Meta::Subsection(m, _)
+            | Meta::Heading(m, _)
+            | Meta::Suffix(m, _) => m.is_named(),
+            Meta::Item(i) => !i.is_pos(),
+            Meta::Skip => true,
+            Meta::CustomUsage(..) | Meta::Strict(_) => false,
+        }
+    }
+}
+
 impl From<Item> for Meta {
     fn from(value: Item) -> Self {
         Meta::Item(Box::new(value))
@@ -320,32 +394,184 @@ impl Meta {
         }
     }
 
-    /// collect different kinds of short names for disambiguation
-    pub(crate) fn collect_shorts(&self, flags: &mut Vec<char>, args: &mut Vec<char>) {
+    /// collect names used to split the command line into items: different kinds of short names
+    /// for disambiguation, single dash long names and long names for slash style options
+    pub(crate) fn collect_names(&self, names: &mut KnownNames) {
         match self {
             Meta::And(xs) | Meta::Or(xs) => {
                 for x in xs {
-                    x.collect_shorts(flags, args);
+                    x.collect_names(names);
                 }
             }
             Meta::Item(m) => match &**m {
                 Item::Any { .. } | Item::Positional { .. } => {}
-                Item::Command { meta, .. } => {
-                    meta.collect_shorts(flags, args);
+                Item::Command {
+                    name,
+                    short,
+                    meta,
+                    info,
+                    ..
+                } => {
+                    // commands of the command itself are only available after its name
+                    let parent = std::mem::take(&mut names.commands);
+                    let relaxed = NameMatch::new(meta, info, names.relaxed.as_ref());
+                    let parent_relaxed = std::mem::replace(&mut names.relaxed, relaxed);
+                    meta.collect_names(names);
+                    let relaxed = std::mem::replace(&mut names.relaxed, parent_relaxed);
+                    let commands = std::mem::replace(&mut names.commands, parent);
+                    names.commands.push(CommandNames {
+                        name,
+                        short: *short,
+                        commands,
+                        relaxed,
+                    });
+                }
+                Item::Flag {
+                    name,
+                    shorts,
+                    longs,
+                    singles,
+                    env,
+                    ..
+                } => {
+                    names.short_flags.extend(shorts);
+                    names.singles.extend(singles);
+                    if let ShortLong::Single(_, true) = name {
+                        names.toggles.extend(singles);
+                    }
+                    names.longs.extend(longs);
+                    names.envs.extend(env);
+                }
+                Item::Argument {
+                    shorts,
+                    longs,
+                    singles,
+                    env,
+                    ..
+                } => {
+                    names.envs.extend(env);
+                    names.short_args.extend(shorts);
+                    names.singles.extend(singles);
+                    names.longs.extend(longs);
+                    names.long_args.extend(longs);
+                    names.long_args.extend(singles);
                 }
-                Item::Flag { shorts, .. } => flags.extend(shorts),
-                Item::Argument { shorts, .. } => args.extend(shorts),
             },
             Meta::CustomUsage(m, _)
             | Meta::Required(m)
             | Meta::Optional(m)
             | Meta::Adjacent(m)
             | Meta::Subsection(m, _)
+            | Meta::Heading(m, _)
             | Meta::Suffix(m, _)
-            | Meta::Many(m) => {
-                m.collect_shorts(flags, args);
+            | Meta::Many(m)
+            | Meta::Bounded(m, _, _) => {
+                m.collect_names(names);
             }
             Meta::Skip | Meta::Strict(_) => {}
         }
     }
+
+    /// collect commands available in the current parser, doesn't look inside of the commands
+    pub(crate) fn collect_commands<'a>(&'a self, res: &mut Vec<&'a Item>) {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => {
+                for x in xs {
+                    x.collect_commands(res);
+                }
+            }
+            Meta::Item(m) => {
+                if let Item::Command { .. } = m.as_ref() {
+                    res.push(m);
+                }
+            }
+            Meta::CustomUsage(m, _)
+            | Meta::Required(m)
+            | Meta::Optional(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Heading(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::Many(m)
+            | Meta::Bounded(m, _, _)
+            | Meta::Strict(m) => {
+                m.collect_commands(res);
+            }
+            Meta::Skip => {}
+        }
+    }
+
+    /// collect global named items available in the current parser
+    ///
+    /// Doesn't look inside of the commands, they pass their own global items further down
+    pub(crate) fn collect_globals(&self, res: &mut Vec<Item>) {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => {
+                for x in xs {
+                    x.collect_globals(res);
+                }
+            }
+            Meta::Item(m) => match &**m {
+                Item::Flag { global: true, .. } | Item::Argument { global: true, .. } => {
+                    res.push(m.as_ref().clone());
+                }
+                Item::Any { .. }
+                | Item::Positional { .. }
+                | Item::Command { .. }
+                | Item::Flag { .. }
+                | Item::Argument { .. } => {}
+            },
+            Meta::CustomUsage(m, _)
+            | Meta::Required(m)
+            | Meta::Optional(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Heading(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::Many(m)
+            | Meta::Bounded(m, _, _)
+            | Meta::Strict(m) => {
+                m.collect_globals(res);
+            }
+            Meta::Skip => {}
+        }
+    }
+
+    /// collect long names and command names available in the current parser
+    ///
+    /// Unlike [`collect_names`](Meta::collect_names) this doesn't look inside of the commands,
+    /// they are handled by their own parsers
+    pub(crate) fn collect_longs(
+        &self,
+        longs: &mut Vec<&'static str>,
+        commands: &mut Vec<&'static str>,
+    ) {
+        match self {
+            Meta::And(xs) | Meta::Or(xs) => {
+                for x in xs {
+                    x.collect_longs(longs, commands);
+                }
+            }
+            Meta::Item(m) => match &**m {
+                Item::Any { .. } | Item::Positional { .. } => {}
+                Item::Command { name, .. } => commands.push(name),
+                Item::Flag { longs: names, .. } | Item::Argument { longs: names, .. } => {
+                    longs.extend(names);
+                }
+            },
+            Meta::CustomUsage(m, _)
+            | Meta::Required(m)
+            | Meta::Optional(m)
+            | Meta::Adjacent(m)
+            | Meta::Subsection(m, _)
+            | Meta::Heading(m, _)
+            | Meta::Suffix(m, _)
+            | Meta::Many(m)
+            | Meta::Bounded(m, _, _)
+            | Meta::Strict(m) => {
+                m.collect_longs(longs, commands);
+            }
+            Meta::Skip => {}
+        }
+    }
 }
@@ -1,10 +1,12 @@
 use std::collections::BTreeSet;
 
 use crate::{
+    args::State,
     buffer::{Block, Doc, Style, Token},
-    info::Info,
+    info::{HelpColumns, HelpLayout, HelpOrder, Info},
     item::{Item, ShortLong},
-    Meta,
+    meta_youmean::is_typo,
+    Meta, OptionStyle,
 };
 
 #[doc(hidden)]
@@ -100,6 +102,8 @@ impl HelpItem<'_> {
 /// Items are stored as references and can be trivially copied
 pub(crate) struct HelpItems<'a> {
     pub(crate) items: Vec<HelpItem<'a>>,
+    /// items with a custom heading, grouped by the heading in order of appearance
+    pub(crate) headings: Vec<(&'static str, Vec<HelpItem<'a>>)>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -166,7 +170,7 @@ impl<'a, 'b> Iterator for HelpItemsIter<'a, 'b> {
 
 impl<'a> HelpItems<'a> {
     #[inline(never)]
-    fn items_of_ty(&self, target: HiTy) -> impl Iterator<Item = &HelpItem> {
+    fn items_of_ty(&self, target: HiTy) -> impl Iterator<Item = &HelpItem<'_>> {
         HelpItemsIter {
             items: &self.items,
             target,
@@ -184,7 +188,9 @@ impl Meta {
             | Meta::Required(x)
             | Meta::Adjacent(x)
             | Meta::Many(x)
+            | Meta::Bounded(x, _, _)
             | Meta::Subsection(x, _)
+            | Meta::Heading(x, _)
             | Meta::Suffix(x, _)
             | Meta::Strict(x)
             | Meta::CustomUsage(x, _) => x.peek_front_ty(),
@@ -218,6 +224,7 @@ impl<'a> HelpItems<'a> {
                 | Meta::Required(x)
                 | Meta::Optional(x)
                 | Meta::Many(x)
+                | Meta::Bounded(x, _, _)
                 | Meta::Strict(x) => go(hi, x, no_ss),
                 Meta::Item(item) => {
                     if matches!(item.as_ref(), Item::Positional { help: None, .. }) {
@@ -230,9 +237,15 @@ impl<'a> HelpItems<'a> {
                         if no_ss {
                             go(hi, m, true);
                         } else {
+                            let start = hi.items.len();
                             hi.items.push(HelpItem::GroupStart { help, ty });
                             go(hi, m, true);
-                            hi.items.push(HelpItem::GroupEnd { ty });
+                            // everything inside went to custom headings
+                            if hi.items.len() == start + 1 {
+                                hi.items.pop();
+                            } else {
+                                hi.items.push(HelpItem::GroupEnd { ty });
+                            }
                         }
                     }
                 }
@@ -242,25 +255,52 @@ impl<'a> HelpItems<'a> {
                         hi.items.push(HelpItem::DecorSuffix { help, ty });
                     }
                 }
+                Meta::Heading(m, name) => {
+                    let mut inner = HelpItems::default();
+                    go(&mut inner, m, false);
+                    for (name, items) in inner.headings {
+                        hi.push_heading(name, items);
+                    }
+                    hi.push_heading(name, inner.items);
+                }
                 Meta::Skip => (),
             }
         }
         go(self, meta, false);
     }
 
-    fn find_group(&self) -> Option<std::ops::RangeInclusive<usize>> {
-        let start = self
-            .items
-            .iter()
-            .position(|i| matches!(i, HelpItem::GroupStart { .. }))?;
-        let end = self
-            .items
+    /// All the items, followed by items with a custom heading
+    pub(crate) fn all(&self) -> impl Iterator<Item = &HelpItem<'a>> {
+        self.items
             .iter()
-            .position(|i| matches!(i, HelpItem::GroupEnd { .. }))?;
-        Some(start..=end)
+            .chain(self.headings.iter().flat_map(|(_, items)| items))
+    }
+
+    fn push_heading(&mut self, name: &'static str, items: Vec<HelpItem<'a>>) {
+        if items.is_empty() {
+            return;
+        }
+        match self.headings.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, xs)) => xs.extend(items),
+            None => self.headings.push((name, items)),
+        }
+    }
+
+    fn find_group(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        find_group(&self.items)
     }
 }
 
+fn find_group(items: &[HelpItem]) -> Option<std::ops::RangeInclusive<usize>> {
+    let start = items
+        .iter()
+        .position(|i| matches!(i, HelpItem::GroupStart { .. }))?;
+    let end = items
+        .iter()
+        .position(|i| matches!(i, HelpItem::GroupEnd { .. }))?;
+    Some(start..=end)
+}
+
 impl From<&Item> for HiTy {
     fn from(value: &Item) -> Self {
         match value {
@@ -306,6 +346,9 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 env,
                 help,
                 shorts: _,
+                longs: _,
+                singles: _,
+                global: _,
             } => Self::Flag {
                 name: *name,
                 env: *env,
@@ -317,6 +360,9 @@ impl<'a> From<&'a Item> for HelpItem<'a> {
                 env,
                 help,
                 shorts: _,
+                longs: _,
+                singles: _,
+                global: _,
             } => Self::Argument {
                 name: *name,
                 metavar: *metavar,
@@ -389,6 +435,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             }
         }
         HelpItem::Positional { metavar, help } => {
+            buf.anchor("pos", metavar.0, vec![metavar.0.to_owned()]);
             buf.token(Token::BlockStart(Block::ItemTerm));
             buf.metavar(*metavar);
             buf.token(Token::BlockEnd(Block::ItemTerm));
@@ -406,6 +453,9 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             #[cfg(feature = "docgen")]
                 info: _,
         } => {
+            let mut names = vec![(*name).to_owned()];
+            names.extend(short.map(String::from));
+            buf.anchor("cmd", name, names);
             buf.token(Token::BlockStart(Block::ItemTerm));
             buf.write_str(name, Style::Literal);
             if let Some(short) = short {
@@ -420,6 +470,7 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             }
         }
         HelpItem::Flag { name, env, help } => {
+            anchor_shortlong(buf, "flag", *name);
             buf.token(Token::BlockStart(Block::ItemTerm));
             write_shortlong(buf, *name);
             buf.token(Token::BlockEnd(Block::ItemTerm));
@@ -454,9 +505,19 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
             env,
             help,
         } => {
+            anchor_shortlong(buf, "arg", *name);
             buf.token(Token::BlockStart(Block::ItemTerm));
             write_shortlong(buf, *name);
-            buf.write_str("=", Style::Text);
+            // single dash names can't have adjacent values
+            // and slash style names use `/name:value`
+            let sep = if let ShortLong::Single(..) = name {
+                " "
+            } else if buf.slash {
+                ":"
+            } else {
+                "="
+            };
+            buf.write_str(sep, Style::Text);
             buf.metavar(*metavar);
             buf.token(Token::BlockEnd(Block::ItemTerm));
 
@@ -500,23 +561,75 @@ fn write_help_item(buf: &mut Doc, item: &HelpItem, include_env: bool) {
     }
 }
 
+/// Anchor for a named item: named after the long name if present, linked from all the names
+fn anchor_shortlong(buf: &mut Doc, kind: &'static str, name: ShortLong) {
+    let short = |s: char| format!("{}{}", buf.short_prefix(), s);
+    let long = |l: &str| format!("{}{}", buf.long_prefix(), l);
+    let (id, names) = match name {
+        ShortLong::Short(s) => (s.to_string(), vec![short(s)]),
+        ShortLong::Long(l) => (l.to_owned(), vec![long(l)]),
+        ShortLong::ShortLong(s, l) => (l.to_owned(), vec![short(s), long(l)]),
+        ShortLong::Single(l, toggle) => {
+            let mut names = vec![format!("-{}", l)];
+            if toggle {
+                names.push(format!("+{}", l));
+            }
+            (l.to_owned(), names)
+        }
+    };
+    buf.anchor(kind, &id, names);
+}
+
 fn write_shortlong(buf: &mut Doc, name: ShortLong) {
     match name {
         ShortLong::Short(s) => {
-            buf.write_char('-', Style::Literal);
+            buf.write_str(buf.short_prefix(), Style::Literal);
             buf.write_char(s, Style::Literal);
         }
         ShortLong::Long(l) => {
-            buf.write_str("    --", Style::Literal);
+            buf.write_str("    ", Style::Literal);
+            buf.write_str(buf.long_prefix(), Style::Literal);
             buf.write_str(l, Style::Literal);
         }
         ShortLong::ShortLong(s, l) => {
-            buf.write_char('-', Style::Literal);
+            buf.write_str(buf.short_prefix(), Style::Literal);
             buf.write_char(s, Style::Literal);
             buf.write_str(", ", Style::Text);
-            buf.write_str("--", Style::Literal);
+            buf.write_str(buf.long_prefix(), Style::Literal);
             buf.write_str(l, Style::Literal);
         }
+        ShortLong::Single(l, toggle) => {
+            buf.write_str(if toggle { "    (+|-)" } else { "    -" }, Style::Literal);
+            buf.write_str(l, Style::Literal);
+        }
+    }
+}
+
+/// Settings of the top level parser that apply to help of all the nested subcommands
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HelpContext {
+    pub(crate) style: OptionStyle,
+    pub(crate) options_first: bool,
+    pub(crate) layout: HelpLayout,
+}
+
+impl From<&Info> for HelpContext {
+    fn from(info: &Info) -> Self {
+        Self {
+            style: info.option_style,
+            options_first: info.options_first,
+            layout: info.layout,
+        }
+    }
+}
+
+impl From<&State> for HelpContext {
+    fn from(args: &State) -> Self {
+        Self {
+            style: args.style,
+            options_first: args.options_first,
+            layout: args.layout,
+        }
     }
 }
 
@@ -527,9 +640,13 @@ pub(crate) fn render_help(
     parser_meta: &Meta,
     help_meta: &Meta,
     include_env: bool,
+    ctx: HelpContext,
 ) -> Doc {
+    let layout = &ctx.layout;
     parser_meta.positional_invariant_check(false);
     let mut buf = Doc::default();
+    buf.slash = ctx.style == OptionStyle::Slash;
+    buf.two_line = layout.columns == HelpColumns::TwoLine;
 
     if let Some(t) = &info.descr {
         buf.token(Token::BlockStart(Block::Block));
@@ -545,11 +662,18 @@ pub(crate) fn render_help(
         buf.write_str(": ", Style::Text);
         buf.token(Token::BlockStart(Block::Mono));
         buf.write_path(path);
-        buf.write_meta(parser_meta, true);
+        buf.write_usage(parser_meta, layout.usage);
         buf.token(Token::BlockEnd(Block::Mono));
     }
     buf.token(Token::BlockEnd(Block::Block));
 
+    if ctx.options_first {
+        buf.token(Token::BlockStart(Block::Block));
+        buf.text("Options are accepted only before the first positional item, ");
+        buf.text("everything after it is treated as positional");
+        buf.token(Token::BlockEnd(Block::Block));
+    }
+
     if let Some(t) = &info.header {
         buf.token(Token::BlockStart(Block::Block));
         buf.doc(t);
@@ -560,7 +684,7 @@ pub(crate) fn render_help(
     items.append_meta(parser_meta);
     items.append_meta(help_meta);
 
-    buf.write_help_item_groups(items, include_env);
+    buf.write_help_item_groups(items, include_env, layout);
 
     if let Some(footer) = &info.footer {
         buf.token(Token::BlockStart(Block::Block));
@@ -570,6 +694,163 @@ pub(crate) fn render_help(
     buf
 }
 
+/// Render items from the parser and all its nested subcommands that mention `keyword`
+///
+/// Items are matched by name or help text ignoring case and allowing for small typos, matches
+/// are grouped by the command path. Returns `None` if nothing matches
+#[inline(never)]
+pub(crate) fn render_search(
+    path: &[String],
+    meta: &Meta,
+    keyword: &str,
+    style: OptionStyle,
+) -> Option<Doc> {
+    fn go(buf: &mut Doc, path: &mut Vec<String>, meta: &Meta, keyword: &str) {
+        let mut items = HelpItems::default();
+        items.append_meta(meta);
+        let mut dd = Dedup::default();
+        let mut found = items
+            .all()
+            .filter(|item| search_matches(item, keyword) && dd.check(item))
+            .peekable();
+        if found.peek().is_some() {
+            buf.token(Token::BlockStart(Block::Block));
+            buf.token(Token::BlockStart(Block::Section2));
+            if path.is_empty() {
+                buf.write_str("Top level:", Style::Emphasis);
+            } else {
+                buf.write_str(&format!("{}:", path.join(" ")), Style::Emphasis);
+            }
+            buf.token(Token::BlockEnd(Block::Section2));
+            buf.token(Token::BlockStart(Block::DefinitionList));
+            for item in found {
+                write_help_item(buf, item, false);
+            }
+            buf.token(Token::BlockEnd(Block::DefinitionList));
+            buf.token(Token::BlockEnd(Block::Block));
+        }
+
+        let mut commands = Vec::new();
+        meta.collect_commands(&mut commands);
+        for item in commands {
+            if let Item::Command { name, meta, .. } = item {
+                path.push((*name).to_owned());
+                go(buf, path, meta, keyword);
+                path.pop();
+            }
+        }
+    }
+
+    let mut buf = Doc::default();
+    buf.slash = style == OptionStyle::Slash;
+    go(&mut buf, &mut path.to_vec(), meta, &keyword.to_lowercase());
+    if buf.is_empty() {
+        None
+    } else {
+        Some(buf)
+    }
+}
+
+/// Check if a help item mentions lowercased `keyword` in its name or help
+fn search_matches(item: &HelpItem, keyword: &str) -> bool {
+    let mut names = Vec::new();
+    let help = match item {
+        HelpItem::Flag { name, help, .. } | HelpItem::Argument { name, help, .. } => {
+            match name {
+                ShortLong::Short(s) => names.push(s.to_string()),
+                ShortLong::Long(l) | ShortLong::Single(l, _) => names.push((*l).to_owned()),
+                ShortLong::ShortLong(s, l) => {
+                    names.push(s.to_string());
+                    names.push((*l).to_owned());
+                }
+            }
+            help
+        }
+        HelpItem::Command {
+            name, short, help, ..
+        } => {
+            names.push((*name).to_owned());
+            names.extend(short.map(String::from));
+            help
+        }
+        HelpItem::Positional { metavar, help } => {
+            names.push(metavar.0.to_owned());
+            help
+        }
+        HelpItem::Any { metavar, help, .. } => {
+            names.push(metavar.monochrome(false));
+            help
+        }
+        HelpItem::DecorSuffix { .. }
+        | HelpItem::GroupStart { .. }
+        | HelpItem::GroupEnd { .. }
+        | HelpItem::AnywhereStart { .. }
+        | HelpItem::AnywhereStop { .. } => return false,
+    };
+
+    let help = help.map_or_else(String::new, |h| h.monochrome(false).to_lowercase());
+    names.iter().any(|name| {
+        let name = name.to_lowercase();
+        name.contains(keyword) || is_typo(keyword, &name)
+    }) || help.contains(keyword)
+        || help
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| is_typo(keyword, word))
+}
+
+/// Name used to sort help items alphabetically
+fn sort_key(item: &HelpItem) -> Option<String> {
+    let key = match item {
+        HelpItem::Flag { name, .. } | HelpItem::Argument { name, .. } => match name {
+            ShortLong::Short(s) => s.to_string(),
+            ShortLong::Long(l) | ShortLong::ShortLong(_, l) | ShortLong::Single(l, _) => {
+                (*l).to_owned()
+            }
+        },
+        HelpItem::Command { name, .. } => (*name).to_owned(),
+        HelpItem::Positional { metavar, .. } => metavar.0.to_owned(),
+        HelpItem::Any { metavar, .. } => metavar.monochrome(false),
+        HelpItem::DecorSuffix { .. }
+        | HelpItem::GroupStart { .. }
+        | HelpItem::GroupEnd { .. }
+        | HelpItem::AnywhereStart { .. }
+        | HelpItem::AnywhereStop { .. } => return None,
+    };
+    Some(key.to_lowercase())
+}
+
+/// Sort help items by name, suffixes stay attached to items they follow and adjacent blocks
+/// are kept together and sorted by their first item
+fn sort_items(items: &mut [HelpItem]) {
+    let mut units: Vec<(Option<String>, Vec<HelpItem>)> = Vec::new();
+    let mut in_block = false;
+    for item in items.iter() {
+        let starts_unit = !in_block
+            && !matches!(
+                item,
+                HelpItem::DecorSuffix { .. } | HelpItem::AnywhereStop { .. }
+            );
+        match item {
+            HelpItem::AnywhereStart { .. } => in_block = true,
+            HelpItem::AnywhereStop { .. } => in_block = false,
+            _ => {}
+        }
+        match units.last_mut() {
+            Some((key, unit)) if !starts_unit => {
+                if key.is_none() {
+                    *key = sort_key(item);
+                }
+                unit.push(*item);
+            }
+            _ => units.push((sort_key(item), vec![*item])),
+        }
+    }
+    units.sort_by(|a, b| a.0.cmp(&b.0));
+    for (slot, item) in items.iter_mut().zip(units.into_iter().flat_map(|u| u.1)) {
+        *slot = item;
+    }
+}
+
 #[derive(Default)]
 struct Dedup {
     items: BTreeSet<String>,
@@ -620,43 +901,139 @@ impl Dedup {
 
 impl Doc {
     #[inline(never)]
-    pub(crate) fn write_help_item_groups(&mut self, mut items: HelpItems, include_env: bool) {
+    pub(crate) fn write_help_item_groups(
+        &mut self,
+        mut items: HelpItems,
+        include_env: bool,
+        layout: &HelpLayout,
+    ) {
         while let Some(range) = items.find_group() {
+            let mut group = items.items.drain(range).collect::<Vec<_>>();
+            let end = group.len() - 1;
+            if layout.order == HelpOrder::Alphabetical {
+                sort_items(&mut group[1..end]);
+            }
             let mut dd = Dedup::default();
-            for item in items.items.drain(range) {
-                if dd.check(&item) {
-                    write_help_item(self, &item, include_env);
+            for item in &group {
+                if dd.check(item) {
+                    write_help_item(self, item, include_env);
                 }
             }
         }
 
+        for (name, xs) in items.headings.drain(..) {
+            self.write_heading(name, xs, include_env, layout.order);
+        }
+
         for (ty, name) in [
-            (HiTy::Positional, "Available positional items:"),
-            (HiTy::Flag, "Available options:"),
-            (HiTy::Command, "Available commands:"),
+            (HiTy::Positional, layout.positionals_title),
+            (HiTy::Flag, layout.options_title),
+            (HiTy::Command, layout.commands_title),
         ] {
-            self.write_help_items(&items, ty, name, include_env);
+            self.write_help_items(name, items.items_of_ty(ty), include_env, layout.order);
         }
     }
 
+    /// Items with a custom heading, groups inside of the heading go after the other items
+    /// with their own titles
     #[inline(never)]
-    fn write_help_items(&mut self, items: &HelpItems, ty: HiTy, name: &str, include_env: bool) {
-        let mut xs = items.items_of_ty(ty).peekable();
-        if xs.peek().is_some() {
-            self.token(Token::BlockStart(Block::Block));
-            self.token(Token::BlockStart(Block::Section2));
-            self.write_str(name, Style::Emphasis);
-            self.token(Token::BlockEnd(Block::Section2));
+    fn write_heading(
+        &mut self,
+        name: &str,
+        mut xs: Vec<HelpItem>,
+        include_env: bool,
+        order: HelpOrder,
+    ) {
+        let mut groups = Vec::new();
+        while let Some(range) = find_group(&xs) {
+            groups.push(xs.drain(range).collect::<Vec<_>>());
+        }
+        if order == HelpOrder::Alphabetical {
+            sort_items(&mut xs);
+        }
+        let mut dd = Dedup::default();
+        self.token(Token::BlockStart(Block::Block));
+        self.token(Token::BlockStart(Block::Section2));
+        self.write_str(name, Style::Emphasis);
+        self.token(Token::BlockEnd(Block::Section2));
+        if !xs.is_empty() {
             self.token(Token::BlockStart(Block::DefinitionList));
-            let mut dd = Dedup::default();
-            for item in xs {
+            for item in &xs {
                 if dd.check(item) {
                     write_help_item(self, item, include_env);
                 }
             }
             self.token(Token::BlockEnd(Block::DefinitionList));
-            self.token(Token::BlockEnd(Block::Block));
         }
+        for mut group in groups {
+            let end = group.len() - 1;
+            if order == HelpOrder::Alphabetical {
+                sort_items(&mut group[1..end]);
+            }
+            if let HelpItem::GroupStart { help, .. } = group[0] {
+                self.token(Token::BlockStart(Block::Section3));
+                self.em_doc(help);
+                self.token(Token::BlockEnd(Block::Section3));
+            }
+            self.token(Token::BlockStart(Block::DefinitionList));
+            for item in &group[1..end] {
+                if dd.check(item) {
+                    write_help_item(self, item, include_env);
+                }
+            }
+            self.token(Token::BlockEnd(Block::DefinitionList));
+        }
+        self.token(Token::BlockEnd(Block::Block));
+    }
+
+    #[inline(never)]
+    fn write_help_items<'a, 'b>(
+        &mut self,
+        name: &str,
+        xs: impl Iterator<Item = &'b HelpItem<'a>>,
+        include_env: bool,
+        order: HelpOrder,
+    ) where
+        'a: 'b,
+    {
+        let mut xs = xs.copied().collect::<Vec<_>>();
+        if xs.is_empty() {
+            return;
+        }
+        if order == HelpOrder::Alphabetical {
+            sort_items(&mut xs);
+        }
+        self.token(Token::BlockStart(Block::Block));
+        self.token(Token::BlockStart(Block::Section2));
+        self.write_str(name, Style::Emphasis);
+        self.token(Token::BlockEnd(Block::Section2));
+        self.token(Token::BlockStart(Block::DefinitionList));
+        let mut dd = Dedup::default();
+        for item in &xs {
+            if dd.check(item) {
+                write_help_item(self, item, include_env);
+            }
+        }
+        self.token(Token::BlockEnd(Block::DefinitionList));
+        self.token(Token::BlockEnd(Block::Block));
+    }
+
+    /// Usage examples as a definition list, command lines are prefixed with `path`
+    pub(crate) fn write_examples(&mut self, path: &[String], examples: &[(&'static str, Doc)]) {
+        if examples.is_empty() {
+            return;
+        }
+        self.token(Token::BlockStart(Block::DefinitionList));
+        for (command, explanation) in examples {
+            self.token(Token::BlockStart(Block::ItemTerm));
+            self.write_path(path);
+            self.write_str(command, Style::Literal);
+            self.token(Token::BlockEnd(Block::ItemTerm));
+            self.token(Token::BlockStart(Block::ItemBody));
+            self.doc(explanation);
+            self.token(Token::BlockEnd(Block::ItemBody));
+        }
+        self.token(Token::BlockEnd(Block::DefinitionList));
     }
 
     pub(crate) fn write_path(&mut self, path: &[String]) {
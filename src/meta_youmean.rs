@@ -1,4 +1,5 @@
 use crate::{
+    args::NameMatch,
     item::ShortLong,
     meta_help::{HelpItem, HelpItems},
     Meta, State,
@@ -10,7 +11,7 @@ pub(crate) enum Variant {
     Flag(ShortLong),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum Suggestion {
     Variant(Variant),
     /// expected --foo, actual -foo
@@ -36,6 +37,12 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
     // it also should be a printable name
     let actual = arg.to_string();
 
+    // names that are already accepted with relaxed matching are not typos
+    let names = args.names.as_deref();
+
+    // slash style names are compared against slash style spellings: `/out` vs `/output`
+    let long_prefix = if actual.starts_with('/') { "/" } else { "--" };
+
     // all the help items one level deep
     let mut hi = HelpItems::default();
     hi.append_meta(meta);
@@ -61,7 +68,7 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
                 // command can result in 2 types of suggestions:
                 // - typo in a short or a long name
                 // - there is a nested command that matches perfectly - try using that
-                let distance = damerau_levenshtein(&actual, name);
+                let distance = name_distance(names, &actual, name);
                 improve(distance, Variant::CommandLong(name));
 
                 // scan nested items and look for exact matches only
@@ -92,7 +99,16 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
             }
             HelpItem::Flag { name, .. } | HelpItem::Argument { name, .. } => {
                 if let Some(long) = name.as_long() {
-                    let distance = damerau_levenshtein(&actual, &format!("--{}", long));
+                    let distance =
+                        name_distance(names, &actual, &format!("{}{}", long_prefix, long));
+                    improve(distance, Variant::Flag(*name));
+                }
+                if let ShortLong::Single(single, toggle) = name {
+                    let mut distance = name_distance(names, &actual, &format!("-{}", single));
+                    if *toggle {
+                        distance =
+                            distance.min(name_distance(names, &actual, &format!("+{}", single)));
+                    }
                     improve(distance, Variant::Flag(*name));
                 }
                 if let Some(short) = name.as_short() {
@@ -135,6 +151,38 @@ pub(crate) fn suggest(args: &State, meta: &Meta) -> Option<(usize, Suggestion)>
     }
 }
 
+/// Distance between a user input and a name
+///
+/// Spellings that differ only in case or in `_` vs `-` are treated as the closest possible typo
+/// so `--Output_Dir` suggests `--output-dir`, unless relaxed matching already accepts them
+fn name_distance(names: Option<&NameMatch>, actual: &str, name: &str) -> usize {
+    let loose = |s: &str| s.to_lowercase().replace('_', "-");
+    if names.map_or(false, |n| n.same(actual, name)) {
+        0
+    } else if actual != name && loose(actual) == loose(name) {
+        1
+    } else {
+        damerau_levenshtein(actual, name)
+    }
+}
+
+/// Check if `actual` looks like `name` with a few typos: one for every four characters
+pub(crate) fn is_typo(actual: &str, name: &str) -> bool {
+    let dist = damerau_levenshtein(actual, name);
+    dist > 0 && dist != usize::MAX && dist * 4 <= actual.chars().count()
+}
+
+/// Name or value closest to `actual` among those that look like a typo, if any
+pub(crate) fn closest<S: AsRef<str>>(
+    actual: &str,
+    candidates: impl IntoIterator<Item = S>,
+) -> Option<S> {
+    candidates
+        .into_iter()
+        .filter(|c| is_typo(actual, c.as_ref()))
+        .min_by_key(|c| damerau_levenshtein(actual, c.as_ref()))
+}
+
 /// Damerau-Levenshtein distance function
 ///
 /// returns `usize::MAX` if there's no common characters at all mostly to avoid
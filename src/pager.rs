@@ -0,0 +1,111 @@
+//! Show long help messages in a pager, see [`pager`](crate::OptionParser::pager)
+
+use std::{
+    io::{IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use crate::buffer::str_width;
+
+/// Pipe `text` through `$PAGER` or `less -FRX` unless it fits on the screen
+///
+/// Returns `false` if text should be printed as usual: stdout is not a terminal, text is known
+/// to fit on the screen or pager can't be started. Screen size comes from `LINES` and `COLUMNS`,
+/// when they are not set the pager decides: `less -F` exits right away if text fits.
+#[clippy::msrv = "1.70"] // `pager` feature needs a newer version
+pub(crate) fn page(text: &str) -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    if let Some((height, width)) = terminal_size() {
+        if screen_rows(text, width) < height {
+            return false;
+        }
+    }
+
+    let (cmd, args) = match pager_command(std::env::var("PAGER").ok()) {
+        Some(pager) => pager,
+        None => return false,
+    };
+    let mut child = match Command::new(cmd).args(args).stdin(Stdio::piped()).spawn() {
+        Ok(child) => child,
+        Err(_) => return false,
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // pager can exit before reading everything, that's fine
+        let _ = stdin.write_all(text.as_bytes());
+        let _ = stdin.write_all(b"\n");
+    }
+    // text is already shown even if pager exits with an error, don't print it again
+    let _ = child.wait();
+    true
+}
+
+/// Program and its arguments to use as a pager: `$PAGER` if set, `less -FRX` otherwise
+fn pager_command(var: Option<String>) -> Option<(String, Vec<String>)> {
+    let pager = var
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| String::from("less -FRX"));
+    let mut words = pager.split_whitespace().map(String::from);
+    Some((words.next()?, words.collect()))
+}
+
+/// Number of terminal rows `text` takes, counting long lines wrapped by the terminal
+fn screen_rows(text: &str, width: usize) -> usize {
+    text.lines()
+        .map(|line| {
+            let w = str_width(&strip_escapes(line));
+            if w == 0 || width == 0 {
+                1
+            } else {
+                (w + width - 1) / width
+            }
+        })
+        .sum()
+}
+
+/// Remove color escape sequences, they take no space on the screen
+fn strip_escapes(line: &str) -> String {
+    let mut res = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequence ends with a letter: ESC [ 1 ; 31 m
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+/// Terminal height and width from `LINES` and `COLUMNS`
+fn terminal_size() -> Option<(usize, usize)> {
+    let var = |name| std::env::var(name).ok()?.parse().ok();
+    Some((var("LINES")?, var("COLUMNS")?))
+}
+
+#[test]
+fn pager_from_env() {
+    let less = Some(("less".to_owned(), vec!["-FRX".to_owned()]));
+    assert_eq!(pager_command(None), less);
+    assert_eq!(pager_command(Some("  ".to_owned())), less);
+    assert_eq!(
+        pager_command(Some("most -s".to_owned())),
+        Some(("most".to_owned(), vec!["-s".to_owned()]))
+    );
+}
+
+#[test]
+fn rows_on_screen() {
+    assert_eq!(screen_rows("a\nb\n\nc", 80), 4);
+    assert_eq!(screen_rows(&"x".repeat(100), 80), 2);
+    assert_eq!(screen_rows(&"x".repeat(80), 80), 1);
+    assert_eq!(screen_rows("\x1b[1mUsage\x1b[0m: app", 10), 1);
+    assert_eq!(screen_rows("日本語日本語", 10), 2);
+}
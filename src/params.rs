@@ -62,7 +62,7 @@
 use std::{ffi::OsString, marker::PhantomData, str::FromStr};
 
 use crate::{
-    args::{Arg, State},
+    args::{Arg, NameMatch, State},
     error::{Message, MissingItem},
     from_os_str::parse_os_str,
     item::ShortLong,
@@ -107,8 +107,11 @@ use crate::{any, command, env, long, positional, short};
 pub struct NamedArg {
     pub(crate) short: Vec<char>,
     pub(crate) long: Vec<&'static str>,
+    pub(crate) single: Vec<&'static str>,
     pub(crate) env: Vec<&'static str>,
     pub(crate) help: Option<Doc>,
+    /// propagated to all the subcommands, see [`global`](NamedArg::global)
+    pub(crate) global: bool,
 }
 
 impl NamedArg {
@@ -118,6 +121,9 @@ impl NamedArg {
             help: self.help.clone(),
             env: self.env.first().copied(),
             shorts: self.short.clone(),
+            longs: self.long.clone(),
+            singles: self.single.clone(),
+            global: self.global,
         })
     }
 }
@@ -141,6 +147,15 @@ impl NamedArg {
         self
     }
 
+    /// Add a single dash long name to a flag/switch/argument/toggle: `-name`
+    ///
+    /// See [`single_dash`](crate::single_dash) for details
+    #[must_use]
+    pub fn single_dash(mut self, name: &'static str) -> Self {
+        self.single.push(name);
+        self
+    }
+
     /// Environment variable fallback
     ///
     /// If named value isn't present - try to fallback to this environment variable.
@@ -161,6 +176,33 @@ impl NamedArg {
         self
     }
 
+    /// Make a `flag`/`switch`/`argument` available in all the nested subcommands
+    ///
+    /// Global items are parsed by the parser they are defined in, but users can put them
+    /// anywhere on the command line, including after the subcommand name: `app build --verbose`.
+    /// Same as other named items they should be placed before the commands in
+    /// [`construct!`](crate::construct!). Help for every nested subcommand lists them in
+    /// "Global options" section and completion offers them at every depth.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let release = long("release").switch();
+    /// let build = construct!(release).to_options().command("build");
+    /// let verbose = short('v').long("verbose").help("Be verbose").global().switch();
+    /// let parser = construct!(verbose, build).to_options();
+    ///
+    /// let r = parser.run_inner(&["build", "--release", "--verbose"]).unwrap();
+    /// assert_eq!(r, (true, true));
+    ///
+    /// let r = parser.run_inner(&["build", "--help"]).unwrap_err().unwrap_stdout();
+    /// assert!(r.contains("Global options"));
+    /// ```
+    #[must_use]
+    pub fn global(mut self) -> Self {
+        self.global = true;
+        self
+    }
+
     /// Add a help message to a `flag`/`switch`/`argument`
     ///
     /// `bpaf` converts doc comments and string into help by following those rules:
@@ -228,6 +270,29 @@ impl NamedArg {
         build_flag_parser(present, None, self)
     }
 
+    /// Toggle that is turned on with `+name` and turned off with `-name`
+    ///
+    /// Works with [`single_dash`](crate::single_dash) names only and fails with a missing item
+    /// error if neither form is present, use [`fallback`](Parser::fallback) to set a default
+    /// value. Help message renders toggles as `(+|-)name`.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let backing = single_dash("backing")
+    ///     .help("Set backing status")
+    ///     .toggle()
+    ///     .fallback(false);
+    /// let parser = backing.to_options();
+    ///
+    /// assert_eq!(parser.run_inner(&["+backing"]).unwrap(), true);
+    /// assert_eq!(parser.run_inner(&["-backing"]).unwrap(), false);
+    /// assert_eq!(parser.run_inner(&[]).unwrap(), false);
+    /// ```
+    #[must_use]
+    pub fn toggle(self) -> impl Parser<bool> {
+        ParseToggle { named: self }
+    }
+
     /// Argument
     ///
     /// A short (`-a`) or long (`--name`) name followed by  either a space or `=` and
@@ -256,10 +321,17 @@ impl NamedArg {
 
     /// `adjacent` requires for the argument to be present in the same word as the flag:
     /// `-f bar` - no, `-fbar` or `-f=bar` - yes.
-    pub(crate) fn matches_arg(&self, arg: &Arg, adjacent: bool) -> bool {
+    ///
+    /// With `names` present long names are matched using relaxed rules
+    pub(crate) fn matches_arg(&self, arg: &Arg, adjacent: bool, names: Option<&NameMatch>) -> bool {
         match arg {
             Arg::Short(s, is_adj, _) => self.short.contains(s) && (!adjacent || *is_adj),
-            Arg::Long(l, is_adj, _) => self.long.contains(&l.as_str()) && (!adjacent || *is_adj),
+            Arg::Long(l, is_adj, _) => {
+                (self.long.contains(&l.as_str())
+                    || names.map_or(false, |n| n.long_matches(&self.long, l)))
+                    && (!adjacent || *is_adj)
+            }
+            Arg::Single(s, plus, _) => !plus && !adjacent && self.single.contains(&s.as_str()),
             Arg::ArgWord(_) | Arg::Word(_) | Arg::PosWord(_) => false,
         }
     }
@@ -306,7 +378,7 @@ impl<T> OptionParser<T> {
         ParseCommand {
             longs: vec![name],
             shorts: Vec::new(),
-            help: self.short_descr().map(Into::into),
+            help: self.short_descr(),
             subparser: self,
             adjacent: false,
         }
@@ -455,11 +527,7 @@ impl<T> Parser<T> for ParseCommand<T> {
                 // narrow down the scope to adjacently available elements
                 args.set_scope(args.adjacently_available_from(args.scope().start + 1));
 
-                match self
-                    .subparser
-                    .run_subparser(args)
-                    .map_err(Message::ParseFailure)
-                {
+                match self.subparser.run_subparser(args) {
                     Ok(ok) => {
                         args.set_scope(orig_args.scope());
                         Ok(ok)
@@ -478,9 +546,7 @@ impl<T> Parser<T> for ParseCommand<T> {
                     }
                 }
             } else {
-                self.subparser
-                    .run_subparser(args)
-                    .map_err(|e| Error(Message::ParseFailure(e)))
+                self.subparser.run_subparser(args).map_err(Error)
             }
         } else {
             #[cfg(feature = "autocomplete")]
@@ -572,6 +638,59 @@ impl<T: Clone + 'static> Parser<T> for ParseFlag<T> {
     }
 }
 
+/// Parser for a `+name`/`-name` toggle, created with [`NamedArg::toggle`]
+#[derive(Clone)]
+struct ParseToggle {
+    named: NamedArg,
+}
+
+impl ParseToggle {
+    fn item(&self) -> Option<Item> {
+        Some(Item::Flag {
+            name: ShortLong::Single(self.named.single.first()?, true),
+            help: self.named.help.clone(),
+            env: None,
+            shorts: Vec::new(),
+            longs: Vec::new(),
+            singles: self.named.single.clone(),
+            global: self.named.global,
+        })
+    }
+}
+
+impl Parser<bool> for ParseToggle {
+    fn eval(&self, args: &mut State) -> Result<bool, Error> {
+        #[cfg(feature = "autocomplete")]
+        let name = self
+            .named
+            .single
+            .first()
+            .map(|n| ShortLong::Single(n, true));
+        if let Some(val) = args.take_toggle(&self.named) {
+            #[cfg(feature = "autocomplete")]
+            if args.touching_last_remove() {
+                args.push_flag_name(name, &self.named.help, self.named.global);
+            }
+            Ok(val)
+        } else {
+            #[cfg(feature = "autocomplete")]
+            args.push_flag_name(name, &self.named.help, self.named.global);
+            let missing = MissingItem {
+                item: self
+                    .item()
+                    .ok_or(Error(Message::ParseFail("toggle without a name")))?,
+                position: args.scope().start,
+                scope: args.scope(),
+            };
+            Err(Error(Message::Missing(vec![missing])))
+        }
+    }
+
+    fn meta(&self) -> Meta {
+        self.item().map_or(Meta::Skip, |item| item.required(true))
+    }
+}
+
 impl<T> ParseFlag<T> {
     /// Add a help message to `flag`
     ///
@@ -641,6 +760,9 @@ impl<T> ParseArgument<T> {
             env: self.named.env.first().copied(),
             help: self.named.help.clone(),
             shorts: self.named.short.clone(),
+            longs: self.named.long.clone(),
+            singles: self.named.single.clone(),
+            global: self.named.global,
         })
     }
 
@@ -649,7 +771,7 @@ impl<T> ParseArgument<T> {
             Ok(Some(w)) => {
                 #[cfg(feature = "autocomplete")]
                 if args.touching_last_remove() {
-                    args.push_metavar(self.metavar, &self.named.help, true);
+                    args.push_metavar(self.metavar, &self.named.help, true, self.named.global);
                 }
                 Ok(w)
             }
@@ -692,7 +814,7 @@ where
         let os = self.take_argument(args)?;
         match parse_os_str::<T>(os) {
             Ok(ok) => Ok(ok),
-            Err(err) => Err(Error(Message::ParseFailed(args.current, err))),
+            Err(err) => Err(Error(Message::ParseFailed(args.current, err.into()))),
         }
     }
 
@@ -804,7 +926,7 @@ fn parse_pos_word(
             }
             #[cfg(feature = "autocomplete")]
             if args.touching_last_remove() && !args.check_no_pos_ahead() {
-                args.push_metavar(metavar.0, help, false);
+                args.push_metavar(metavar.0, help, false, false);
                 args.set_no_pos_ahead();
             }
             Ok(word)
@@ -812,7 +934,7 @@ fn parse_pos_word(
         Err(err) => {
             #[cfg(feature = "autocomplete")]
             if !args.check_no_pos_ahead() {
-                args.push_metavar(metavar.0, help, false);
+                args.push_metavar(metavar.0, help, false, false);
                 args.set_no_pos_ahead();
             }
             Err(err)
@@ -829,7 +951,7 @@ where
         let os = parse_pos_word(args, self.strict, self.metavar, &self.help)?;
         match parse_os_str::<T>(os) {
             Ok(ok) => Ok(ok),
-            Err(err) => Err(Error(Message::ParseFailed(args.current, err))),
+            Err(err) => Err(Error(Message::ParseFailed(args.current, err.into()))),
         }
     }
 
@@ -891,7 +1013,9 @@ impl<T> Parser<T> for ParseAny<T> {
         for (ix, x) in args.items_iter() {
             let (os, next) = match x {
                 Arg::Short(_, next, os) | Arg::Long(_, next, os) => (os, *next),
-                Arg::ArgWord(os) | Arg::Word(os) | Arg::PosWord(os) => (os, false),
+                Arg::Single(_, _, os) | Arg::ArgWord(os) | Arg::Word(os) | Arg::PosWord(os) => {
+                    (os, false)
+                }
             };
             if let Some(i) = (self.check)(os.clone()) {
                 args.remove(ix);
@@ -917,3 +1041,131 @@ impl<T> Parser<T> for ParseAny<T> {
         Meta::Item(Box::new(self.item()))
     }
 }
+
+/// Where [`pass_through`](crate::pass_through) looks for unknown items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Anywhere,
+    BeforeKnown,
+    AfterKnown,
+}
+
+/// Collect all the unconsumed items, created with [`pass_through`](crate::pass_through)
+pub struct ParsePassThrough {
+    metavar: Doc,
+    help: Option<Doc>,
+    placement: Placement,
+}
+
+impl ParsePassThrough {
+    pub(crate) fn new() -> Self {
+        Self {
+            metavar: [("ARGS", crate::buffer::Style::Metavar)][..].into(),
+            help: None,
+            placement: Placement::Anywhere,
+        }
+    }
+
+    fn item(&self) -> Item {
+        Item::Any {
+            metavar: self.metavar.clone(),
+            help: self.help.clone(),
+            anywhere: true,
+        }
+    }
+
+    /// Add a help message to [`pass_through`](crate::pass_through) parser
+    #[must_use]
+    pub fn help<M: Into<Doc>>(mut self, help: M) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Replace metavar with a custom value, default is `ARGS`
+    #[must_use]
+    pub fn metavar<M: Into<Doc>>(mut self, metavar: M) -> Self {
+        self.metavar = metavar.into();
+        self
+    }
+
+    /// Collect only unknown items that are present before the first known one
+    ///
+    /// Unknown items after the first known item are reported as errors as usual
+    #[must_use]
+    pub fn before_known(mut self) -> Self {
+        self.placement = Placement::BeforeKnown;
+        self
+    }
+
+    /// Collect only unknown items that are present after the last known one
+    ///
+    /// Unknown items before the last known item are reported as errors as usual
+    #[must_use]
+    pub fn after_known(mut self) -> Self {
+        self.placement = Placement::AfterKnown;
+        self
+    }
+}
+
+impl Parser<Vec<OsString>> for ParsePassThrough {
+    fn eval(&self, args: &mut State) -> Result<Vec<OsString>, Error> {
+        // known items are the ones consumed by other parsers, "--" doesn't count
+        let known = |ix: &usize| {
+            args.present(*ix) == Some(false)
+                && !matches!(&args.items[*ix], Arg::PosWord(w) if w == "--")
+        };
+        let allowed = match self.placement {
+            Placement::Anywhere => 0..usize::MAX,
+            Placement::BeforeKnown => 0..args.scope().find(known).unwrap_or(usize::MAX),
+            Placement::AfterKnown => {
+                args.scope().rev().find(known).map_or(0, |ix| ix + 1)..usize::MAX
+            }
+        };
+
+        let mut res = Vec::new();
+        let mut taken = Vec::new();
+        let mut skip_next = false;
+        for (ix, arg) in args.items_iter() {
+            if std::mem::take(&mut skip_next)
+                || !allowed.contains(&ix)
+                || args
+                    .reserved
+                    .iter()
+                    .any(|r| r.matches_arg(arg, false, args.names.as_deref()))
+            {
+                continue;
+            }
+            let os = match arg {
+                // value is a part of the original item: `-fbar` or `--key=val`
+                Arg::Short(_, true, os) | Arg::Long(_, true, os) => {
+                    skip_next = true;
+                    taken.push(ix + 1);
+                    os.clone()
+                }
+                // short flags can be a part of a group, `-abc`, restore them one by one
+                Arg::Short(_, false, _) => OsString::from(arg.to_string()),
+                Arg::Long(_, false, os)
+                | Arg::Single(_, _, os)
+                | Arg::ArgWord(os)
+                | Arg::Word(os)
+                | Arg::PosWord(os) => os.clone(),
+            };
+            taken.push(ix);
+            res.push(os);
+        }
+        for ix in taken {
+            args.remove(ix);
+        }
+        Ok(res)
+    }
+
+    fn meta(&self) -> Meta {
+        // usage line is `[ARGS passed through]`, help lists just the metavar
+        let mut usage = Doc::default();
+        usage.text("[");
+        usage.doc(&self.metavar);
+        usage.text(" passed through]");
+        let item = Meta::Optional(Box::new(Meta::Item(Box::new(self.item()))));
+        Meta::CustomUsage(Box::new(item), Box::new(usage))
+    }
+}
@@ -39,6 +39,13 @@ where
                         Err(e) => Err(Error(Message::PureFailed(e.to_string()))),
                     }
                 } else {
+                    // items consumed before the failure are not unexpected when several errors
+                    // are reported at once
+                    if args.max_errors > 1 {
+                        #[cfg(feature = "autocomplete")]
+                        args.swap_comps(&mut clone);
+                        std::mem::swap(args, &mut clone);
+                    }
                     Err(Error(e))
                 }
             }
@@ -112,6 +119,26 @@ where
     }
 }
 
+/// Parser with items listed under a custom heading in help, created with
+/// [`heading`](Parser::heading).
+pub struct ParseHeading<P> {
+    pub(crate) inner: P,
+    pub(crate) heading: &'static str,
+}
+
+impl<T, P> Parser<T> for ParseHeading<P>
+where
+    P: Parser<T>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        self.inner.eval(args)
+    }
+
+    fn meta(&self) -> Meta {
+        Meta::Heading(Box::new(self.inner.meta()), self.heading)
+    }
+}
+
 /// Apply inner parser several times and collect results into `Vec`, created with
 /// [`some`](Parser::some), requires for at least one item to be available to succeed.
 /// Implements [`catch`](ParseMany::catch)
@@ -168,6 +195,8 @@ where
 pub struct ParseCollect<P, C, T> {
     pub(crate) inner: P,
     pub(crate) catch: bool,
+    pub(crate) min: usize,
+    pub(crate) max: usize,
     pub(crate) ctx: PhantomData<(C, T)>,
 }
 
@@ -186,6 +215,42 @@ impl<T, C, P> ParseCollect<P, C, T> {
         self.catch = true;
         self
     }
+
+    #[must_use]
+    /// Require at least `min` items to be present
+    ///
+    /// See [`ParseMany::at_least`] for details, behavior should be identical.
+    ///
+    /// # Panics
+    /// If `min` is greater than the maximum set with [`at_most`](ParseCollect::at_most)
+    pub fn at_least(mut self, min: usize) -> Self {
+        assert!(
+            min <= self.max,
+            "at_least({}) is above at_most({})",
+            min,
+            self.max
+        );
+        self.min = min;
+        self
+    }
+
+    #[must_use]
+    /// Stop consuming items after collecting `max` of them
+    ///
+    /// See [`ParseMany::at_most`] for details, behavior should be identical.
+    ///
+    /// # Panics
+    /// If `max` is less than the minimum set with [`at_least`](ParseCollect::at_least)
+    pub fn at_most(mut self, max: usize) -> Self {
+        assert!(
+            self.min <= max,
+            "at_most({}) is below at_least({})",
+            max,
+            self.min
+        );
+        self.max = max;
+        self
+    }
 }
 
 impl<T, C, P> Parser<C> for ParseCollect<P, C, T>
@@ -195,12 +260,23 @@ where
 {
     fn eval(&self, args: &mut State) -> Result<C, Error> {
         let mut len = usize::MAX;
-        std::iter::from_fn(|| parse_option(&self.inner, &mut len, args, self.catch).transpose())
-            .collect::<Result<C, Error>>()
+        let mut got = 0;
+        let res = std::iter::from_fn(|| {
+            parse_option(&self.inner, &mut len, args, self.catch).transpose()
+        })
+        .take(self.max)
+        .inspect(|_| got += 1)
+        .collect::<Result<C, Error>>()?;
+        check_bounds(&self.inner, args, got, self.min, self.max)?;
+        Ok(res)
     }
 
     fn meta(&self) -> Meta {
-        Meta::Many(Box::new(Meta::Required(Box::new(self.inner.meta()))))
+        if self.min == 0 && self.max == usize::MAX {
+            Meta::Many(Box::new(Meta::Required(Box::new(self.inner.meta()))))
+        } else {
+            bounded_meta(self.inner.meta(), self.min, self.max)
+        }
     }
 }
 
@@ -352,6 +428,10 @@ fn this_or_that_picks_first(
         }
     }
 
+    // branch to keep the state of if both branches fail, only matters for reporting items left
+    // unconsumed when several errors are reported at once
+    let mut failed = None;
+
     // otherwise pick based on the left most or successful one
     #[allow(clippy::let_and_return)] // <- it is without autocomplete only
     let res = match (err_a, err_b) {
@@ -362,7 +442,14 @@ fn this_or_that_picks_first(
                 Ok(args_a.pick_winner(args_b))
             }
         }
-        (Some(e1), Some(e2)) => Err(e1.combine_with(e2)),
+        (Some(e1), Some(e2)) => {
+            if !e1.0.can_catch() {
+                failed = Some(true);
+            } else if !e2.0.can_catch() {
+                failed = Some(false);
+            }
+            Err(e1.combine_with(e2))
+        }
         // otherwise either a or b are success, true means a is success
         (a_ok, _) => Ok((a_ok.is_none(), None)),
     };
@@ -407,8 +494,13 @@ fn this_or_that_picks_first(
             }
             std::mem::swap(args, args_b);
         }
-        // no winner, keep the completions but don't touch args otherwise
-        Err(_) => {}
+        // no winner, keep the completions but don't touch args otherwise unless the failure
+        // can't be handled and items consumed by the failed branch are not to be reported again
+        Err(_) => match failed {
+            Some(true) if args.max_errors > 1 => std::mem::swap(args, args_a),
+            Some(false) if args.max_errors > 1 => std::mem::swap(args, args_b),
+            _ => {}
+        },
     }
 
     #[cfg(feature = "autocomplete")]
@@ -439,7 +531,39 @@ where
         let t = self.inner.eval(args)?;
         match (self.parse_fn)(t) {
             Ok(r) => Ok(r),
-            Err(e) => Err(Error(Message::ParseFailed(args.current, e.to_string()))),
+            Err(e) => Err(Error(Message::ParseFailed(
+                args.current,
+                e.to_string().into(),
+            ))),
+        }
+    }
+
+    fn meta(&self) -> Meta {
+        self.inner.meta()
+    }
+}
+
+/// Parser that transforms parsed value with a failing function with a styled error message,
+/// created with [`parse_styled`](Parser::parse_styled)
+pub struct ParseStyled<T, P, F, E, R> {
+    pub(crate) inner: P,
+    pub(crate) inner_res: PhantomData<T>,
+    pub(crate) parse_fn: F,
+    pub(crate) res: PhantomData<R>,
+    pub(crate) err: PhantomData<E>,
+}
+
+impl<T, P, F, E, R> Parser<R> for ParseStyled<T, P, F, E, R>
+where
+    P: Parser<T>,
+    F: Fn(T) -> Result<R, E>,
+    E: Into<Doc>,
+{
+    fn eval(&self, args: &mut State) -> Result<R, Error> {
+        let t = self.inner.eval(args)?;
+        match (self.parse_fn)(t) {
+            Ok(r) => Ok(r),
+            Err(e) => Err(Error(Message::ParseFailed(args.current, e.into()))),
         }
     }
 
@@ -474,6 +598,13 @@ where
                 if e.can_catch() {
                     Ok(self.value.clone())
                 } else {
+                    // items consumed before the failure are not unexpected when several errors
+                    // are reported at once
+                    if args.max_errors > 1 {
+                        #[cfg(feature = "autocomplete")]
+                        args.swap_comps(&mut clone);
+                        std::mem::swap(args, &mut clone);
+                    }
                     Err(Error(e))
                 }
             }
@@ -570,7 +701,40 @@ where
         if (self.check)(&t) {
             Ok(t)
         } else {
-            Err(Error(Message::GuardFailed(args.current, self.message)))
+            Err(Error(Message::GuardFailed(
+                args.current,
+                self.message.into(),
+            )))
+        }
+    }
+
+    fn meta(&self) -> Meta {
+        self.inner.meta()
+    }
+}
+
+/// Parser fails with a message computed from the value if check returns false, created with
+/// [`guard_with`](Parser::guard_with).
+pub struct ParseGuardWith<P, F, M> {
+    pub(crate) inner: P,
+    pub(crate) check: F,
+    pub(crate) message: M,
+}
+
+impl<T, P, F, M, D> Parser<T> for ParseGuardWith<P, F, M>
+where
+    P: Parser<T>,
+    F: Fn(&T) -> bool,
+    M: Fn(&T) -> D,
+    D: Into<Doc>,
+{
+    fn eval(&self, args: &mut State) -> Result<T, Error> {
+        let t = self.inner.eval(args)?;
+        if (self.check)(&t) {
+            Ok(t)
+        } else {
+            let message = (self.message)(&t).into();
+            Err(Error(Message::GuardFailed(args.current, message)))
         }
     }
 
@@ -690,6 +854,8 @@ impl<P> ParseOptional<P> {
 pub struct ParseMany<P> {
     pub(crate) inner: P,
     pub(crate) catch: bool,
+    pub(crate) min: usize,
+    pub(crate) max: usize,
 }
 
 impl<P> ParseMany<P> {
@@ -707,6 +873,76 @@ impl<P> ParseMany<P> {
         self.catch = true;
         self
     }
+
+    #[must_use]
+    /// Require at least `min` items to be present
+    ///
+    /// Parser fails with an error that names the expected number of items if fewer than `min`
+    /// of them are present. When none are present at all this failure can be handled by
+    /// [`optional`](Parser::optional) or [`fallback`](Parser::fallback) just like a missing
+    /// item.
+    ///
+    /// Usage line renders the cardinality after the item: `FILE{2,}`, combined with
+    /// [`at_most`](ParseMany::at_most) - `FILE{2,5}` or `FILE{3}` if both values are the same.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let peer = long("peer").argument::<String>("PEER").many().at_least(2).at_most(5);
+    /// let parser = peer.to_options();
+    ///
+    /// let r = parser.run_inner(&["--peer", "a", "--peer", "b"]).unwrap();
+    /// assert_eq!(r, ["a", "b"]);
+    ///
+    /// let r = parser.run_inner(&["--peer", "a"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "expected 2 to 5 occurrences of `--peer=PEER`, got 1");
+    /// ```
+    ///
+    /// # Panics
+    /// If `min` is greater than the maximum set with [`at_most`](ParseMany::at_most)
+    pub fn at_least(mut self, min: usize) -> Self {
+        assert!(
+            min <= self.max,
+            "at_least({}) is above at_most({})",
+            min,
+            self.max
+        );
+        self.min = min;
+        self
+    }
+
+    #[must_use]
+    /// Stop consuming items after collecting `max` of them
+    ///
+    /// Anything past the first `max` items is left untouched for other parsers to consume.
+    /// This makes it possible to have a fixed number of positional items followed by more
+    /// positional items. If nothing else consumes them parser fails with an error that names
+    /// the expected number of items.
+    ///
+    /// ```rust
+    /// # use bpaf::*;
+    /// let src = positional::<String>("SRC").many().at_least(2).at_most(2);
+    /// let dst = positional::<String>("DST");
+    /// let parser = construct!(src, dst).to_options();
+    ///
+    /// let r = parser.run_inner(&["a", "b", "c"]).unwrap();
+    /// assert_eq!(r, (vec!["a".to_owned(), "b".to_owned()], "c".to_owned()));
+    ///
+    /// let r = parser.run_inner(&["a", "b", "c", "d"]).unwrap_err().unwrap_stderr();
+    /// assert_eq!(r, "expected exactly 2 occurrences of `SRC`, got 3");
+    /// ```
+    ///
+    /// # Panics
+    /// If `max` is less than the minimum set with [`at_least`](ParseMany::at_least)
+    pub fn at_most(mut self, max: usize) -> Self {
+        assert!(
+            self.min <= max,
+            "at_most({}) is below at_least({})",
+            max,
+            self.min
+        );
+        self.max = max;
+        self
+    }
 }
 
 /// try to parse
@@ -758,18 +994,61 @@ where
     }
 }
 
+/// Make sure bounded repetition collected enough items
+///
+/// Repetition that stopped at the maximum is remembered so items past it can be reported with
+/// the expected count if nothing else consumes them
+fn check_bounds<P, T>(
+    parser: &P,
+    args: &mut State,
+    got: usize,
+    min: usize,
+    max: usize,
+) -> Result<(), Error>
+where
+    P: Parser<T>,
+{
+    if got < min {
+        let meta = Box::new(parser.meta());
+        Err(Error(Message::Cardinality(meta, got, min, max)))
+    } else {
+        if got == max {
+            args.bounded.push((Box::new(parser.meta()), min, max));
+        }
+        Ok(())
+    }
+}
+
+fn bounded_meta(inner: Meta, min: usize, max: usize) -> Meta {
+    let inner = if min == 0 {
+        Meta::Optional(Box::new(inner))
+    } else {
+        Meta::Required(Box::new(inner))
+    };
+    Meta::Bounded(Box::new(inner), min, max)
+}
+
 impl<T, P> Parser<Vec<T>> for ParseMany<P>
 where
     P: Parser<T>,
 {
     fn eval(&self, args: &mut State) -> Result<Vec<T>, Error> {
         let mut len = usize::MAX;
-        std::iter::from_fn(|| parse_option(&self.inner, &mut len, args, self.catch).transpose())
-            .collect::<Result<Vec<T>, Error>>()
+        let res = std::iter::from_fn(|| {
+            parse_option(&self.inner, &mut len, args, self.catch).transpose()
+        })
+        .take(self.max)
+        .collect::<Result<Vec<T>, Error>>()?;
+        check_bounds(&self.inner, args, res.len(), self.min, self.max)?;
+        Ok(res)
     }
 
     fn meta(&self) -> Meta {
-        Meta::Many(Box::new(Meta::Optional(Box::new(self.inner.meta()))))
+        if self.min == 0 && self.max == usize::MAX {
+            Meta::Many(Box::new(Meta::Optional(Box::new(self.inner.meta()))))
+        } else {
+            bounded_meta(self.inner.meta(), self.min, self.max)
+        }
     }
 }
 
@@ -869,7 +1148,6 @@ where
 
 impl<T> ParseCon<T> {
     #[must_use]
-
     /// Automagically restrict the inner parser scope to accept adjacent values only
     ///
     /// `adjacent` can solve surprisingly wide variety of problems: sequential command chaining,
@@ -968,6 +1246,12 @@ where
         // restore old, now metavars added by inner parser, if any, are in comp_items
         args.swap_comps_with(&mut comp_items);
 
+        if let Some(ix) = args.current {
+            if !args.completed.contains(&ix) {
+                args.completed.push(ix);
+            }
+        }
+
         if let Some(comp) = &mut args.comp_mut() {
             if res.is_err() {
                 comp.extend_comps(comp_items);
@@ -977,6 +1261,13 @@ where
 
         let res = res?;
 
+        // looking for a replacement of a value that failed to parse later on
+        if let Some((ix, candidates)) = &mut args.candidates {
+            if args.current == Some(*ix) {
+                candidates.extend((self.op)(&res).into_iter().map(|(r, _)| r.into()));
+            }
+        }
+
         // completion function generates suggestions based on the parsed inner value, for
         // that `res` must contain a parsed value
         let depth = args.depth();
@@ -1425,3 +1425,268 @@ fn positionals_with_no_completions_are_not_duplicated() {
 \tBETA\t\tBeta argument\n\n"
     );
 }
+
+#[test]
+fn abbreviated_names_complete() {
+    let verbose = long("verbose").help("Be verbose").switch();
+    let version = long("version-info").help("Show version").switch();
+    let parser = construct!(verbose, version).to_options().abbreviations();
+
+    let r = parser
+        .run_inner(Args::from(&["--verb"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--verbose");
+
+    let r = parser
+        .run_inner(Args::from(&["--ver"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+--verbose\t--verbose\t\tBe verbose
+--version-info\t--version-info\t\tShow version\n\n"
+    );
+}
+
+#[test]
+fn single_dash_names_complete() {
+    let name = single_dash("name")
+        .help("File name pattern")
+        .argument::<String>("PATTERN")
+        .optional();
+    let newer = single_dash("newer")
+        .help("Newer than FILE")
+        .argument::<String>("FILE");
+    let backing = single_dash("backing").help("Set backing").toggle();
+    let parser = construct!(name, newer, backing).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["-n"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "\
+-name\t-name PATTERN\t\tFile name pattern
+-newer\t-newer FILE\t\tNewer than FILE\n\n"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&["-na"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "-name");
+
+    let r = parser
+        .run_inner(Args::from(&["+b"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "+backing");
+
+    let r = parser
+        .run_inner(Args::from(&["-back"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "-backing");
+}
+
+#[test]
+fn slash_style_complete() {
+    fn parser(style: OptionStyle) -> OptionParser<(bool, String)> {
+        let verbose = short('v').long("verbose").help("Be verbose").switch();
+        let out = long("out")
+            .help("Output file")
+            .argument::<String>("FILE")
+            .complete(|_| vec![("file.txt", None)]);
+        construct!(verbose, out).to_options().option_style(style)
+    }
+
+    let r = parser(OptionStyle::Slash)
+        .run_inner(Args::from(&["/v"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "/verbose");
+
+    let r = parser(OptionStyle::Slash)
+        .run_inner(Args::from(&["/o"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "/out");
+
+    let r = parser(OptionStyle::Slash)
+        .run_inner(Args::from(&["/out:f"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "/out:file.txt");
+
+    let r = parser(OptionStyle::DashAndSlash)
+        .run_inner(Args::from(&["/"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "/verbose\t/verbose\t\tBe verbose\n/out\t/out:FILE\t\tOutput file\n\n"
+    );
+
+    let r = parser(OptionStyle::DashAndSlash)
+        .run_inner(Args::from(&["--v"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--verbose");
+}
+
+#[test]
+fn options_first_complete() {
+    let verbose = short('v').long("verbose").help("Be verbose").switch();
+    let cmd = positional::<String>("CMD")
+        .complete(|s| {
+            ["ls", "lsblk"]
+                .into_iter()
+                .filter(|c| c.starts_with(s.as_str()))
+                .map(|c| (c, None))
+                .collect()
+        })
+        .many();
+    let parser = construct!(verbose, cmd).to_options().options_first();
+
+    let r = parser
+        .run_inner(Args::from(&["--v"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--verbose");
+
+    let r = parser
+        .run_inner(Args::from(&["ls", "--v"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tCMD\t\t\n\n");
+
+    let r = parser
+        .run_inner(Args::from(&["-v", "l"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "\tCMD\t\t\nls\tls\t\t\nlsblk\tlsblk\t\t\n\n");
+}
+
+#[test]
+fn global_options_complete() {
+    let release = long("release").help("Build in release mode").switch();
+    let build = construct!(release)
+        .to_options()
+        .command("build")
+        .help("Build the project");
+    let verbose = long("verbose").help("Be verbose").global().switch();
+    let quiet = long("quiet").help("Be quiet").switch();
+    let parser = construct!(verbose, quiet, build).to_options();
+
+    let r = parser
+        .run_inner(Args::from(&["build", "--"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(
+        r,
+        "--verbose\t--verbose\t\tBe verbose\n--release\t--release\t\tBuild in release mode\n\n"
+    );
+
+    let r = parser
+        .run_inner(Args::from(&["build", "--v"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "--verbose");
+}
+
+#[test]
+fn help_subcommand_complete() {
+    let deploy = pure(())
+        .to_options()
+        .command("deploy")
+        .help("Deploy the build");
+    let build = construct!(deploy)
+        .to_options()
+        .command("build")
+        .help("Build the project");
+    let parser = build.to_options().help_command();
+
+    let r = parser
+        .run_inner(Args::from(&["he"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "help");
+
+    let r = parser
+        .run_inner(Args::from(&["help", ""]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "build");
+
+    let r = parser
+        .run_inner(Args::from(&["help", "build", "d"]).set_comp(0))
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, "deploy");
+}
+
+#[test]
+fn suggest_value_from_completion() {
+    fn modes(input: &String) -> Vec<(&'static str, Option<&'static str>)> {
+        ["always", "auto", "never"]
+            .iter()
+            .filter(|m| m.starts_with(input.as_str()))
+            .map(|m| (*m, None))
+            .collect()
+    }
+    let color = long("color")
+        .argument::<String>("WHEN")
+        .complete(modes)
+        .guard(|m| modes(m).len() == 1, "not a valid mode");
+    let jobs = long("jobs")
+        .argument::<String>("N")
+        .complete(|_| vec![("512", None), ("1024", None), ("2048", None)])
+        .parse(|s| s.parse::<usize>());
+    let parser = construct!(color, jobs).to_options();
+
+    let r = parser
+        .run_inner(&["--color", "nevr", "--jobs", "4"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`nevr`: not a valid mode, did you mean `never`?");
+
+    let r = parser
+        .run_inner(&["--color", "auto", "--jobs", "1024x"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "couldn't parse `1024x`: invalid digit found in string, did you mean `1024`?"
+    );
+
+    // nothing close enough
+    let r = parser
+        .run_inner(&["--color", "sometimes", "--jobs", "4"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`sometimes`: not a valid mode");
+}
+
+#[test]
+fn no_second_pass_without_completion() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let jobs = long("jobs").argument::<String>("N").parse(move |s| {
+        counter.set(counter.get() + 1);
+        s.parse::<usize>()
+    });
+    let parser = jobs.to_options();
+
+    let r = parser
+        .run_inner(&["--jobs", "4x"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "couldn't parse `4x`: invalid digit found in string");
+    assert_eq!(calls.get(), 1);
+}
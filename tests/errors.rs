@@ -1,3 +1,4 @@
+use bpaf::doc::Style;
 use bpaf::*;
 
 #[test]
@@ -242,3 +243,512 @@ fn strictly_positional_help() {
         .unwrap_stderr();
     assert_eq!(r, "`--help` is not expected in this context");
 }
+
+#[test]
+fn bounded_many_errors() {
+    let parser = positional::<String>("FILE")
+        .many()
+        .at_least(2)
+        .at_most(3)
+        .to_options();
+
+    let r = parser.run_inner(&["a"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "expected 2 to 3 occurrences of `FILE`, got 1");
+
+    let r = parser.run_inner(&["a", "b", "c"]).unwrap();
+    assert_eq!(r, ["a", "b", "c"]);
+
+    let r = parser
+        .run_inner(&["a", "b", "c", "d"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "expected 2 to 3 occurrences of `FILE`, got 4");
+
+    let parser = short('p')
+        .argument::<u32>("P")
+        .many()
+        .at_least(3)
+        .at_most(3)
+        .to_options();
+    let r = parser
+        .run_inner(&["-p", "1", "-p", "2"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "expected exactly 3 occurrences of `-p=P`, got 2");
+}
+
+#[test]
+fn bounded_many_over_the_max() {
+    let parser = short('x')
+        .argument::<u32>("X")
+        .many()
+        .at_least(1)
+        .at_most(2)
+        .to_options();
+    let r = parser
+        .run_inner(&["-x", "1", "-x", "2", "-x", "3"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "expected 1 to 2 occurrences of `-x=X`, got 3");
+
+    let parser = short('x')
+        .argument::<u32>("X")
+        .collect::<Vec<_>>()
+        .at_most(1)
+        .to_options();
+    let r = parser
+        .run_inner(&["-x", "1", "-x", "2", "-x", "3"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "expected at most 1 occurrence of `-x=X`, got 3");
+
+    let parser = short('x')
+        .argument::<u32>("X")
+        .many()
+        .at_least(1)
+        .at_most(1)
+        .to_options();
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "expected exactly 1 occurrence of `-x=X`, got 0");
+}
+
+#[test]
+#[should_panic(expected = "at_most(1) is below at_least(3)")]
+fn bounded_many_min_above_max() {
+    let _ = short('x')
+        .argument::<u32>("X")
+        .many()
+        .at_least(3)
+        .at_most(1);
+}
+
+#[test]
+fn bounded_many_missing_is_catchable() {
+    let parser = short('p')
+        .argument::<u32>("P")
+        .many()
+        .at_least(2)
+        .optional()
+        .to_options();
+
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, None);
+
+    let r = parser.run_inner(&["-p", "1"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "expected at least 2 occurrences of `-p=P`, got 1");
+}
+
+#[test]
+fn ambiguous_prefix() {
+    let verbose = long("verbose").switch();
+    let version = long("version-info").switch();
+    let verify = long("verify").argument::<String>("HASH").optional();
+    let parser = construct!(verbose, version, verify)
+        .to_options()
+        .abbreviations();
+
+    let r = parser.run_inner(&["--ver"]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "`--ver` is ambiguous, could be `--verbose`, `--verify` or `--version-info`"
+    );
+
+    let r = parser
+        .run_inner(&["--veri", "x", "--verb", "--ve"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "`--ve` is ambiguous, could be `--verbose`, `--verify` or `--version-info`"
+    );
+}
+
+#[test]
+fn diagnostic_conflict() {
+    let a = short('a').req_flag(());
+    let b = short('b').req_flag(());
+    let parser = construct!([a, b])
+        .to_options()
+        .error_style(ErrorStyle::Diagnostic);
+
+    let r = parser.run_inner(&["-a", "-b"]).unwrap_err().unwrap_stderr();
+    let expected = "\
+`-b` cannot be used at the same time as `-a`
+
+    -a -b
+    -- ^^
+
+usage: (-a | -b)";
+    assert_eq!(r, expected);
+
+    let r = parser.run_inner(&["-ab"]).unwrap_err().unwrap_stderr();
+    let expected = "\
+`-b` cannot be used at the same time as `-a`
+
+    -ab
+    --^
+
+usage: (-a | -b)";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn diagnostic_attached_values() {
+    let verbose = short('v').switch();
+    let jobs = short('j').long("jobs").argument::<usize>("N");
+    let parser = construct!(verbose, jobs)
+        .to_options()
+        .error_style(ErrorStyle::Diagnostic);
+
+    let r = parser.run_inner(&["-vjx"]).unwrap_err().unwrap_stderr();
+    let expected = "\
+couldn't parse `x`: invalid digit found in string
+
+    -vjx
+       ^
+
+usage: [-v] -j=N";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--jobs=many", "-v"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+couldn't parse `many`: invalid digit found in string
+
+    --jobs=many -v
+           ^^^^
+
+usage: [-v] -j=N";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["-j", "1", "hello world"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+`hello world` is not expected in this context
+
+    -j 1 'hello world'
+          ^^^^^^^^^^^
+
+usage: [-v] -j=N";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn diagnostic_subcommand() {
+    let name = positional::<String>("NAME");
+    let force = short('f').long("force").switch();
+    let add = construct!(force, name)
+        .to_options()
+        .command("add")
+        .help("Add a package");
+    let verbose = short('v').switch();
+    let parser = construct!(verbose, add)
+        .to_options()
+        .error_style(ErrorStyle::Diagnostic);
+
+    let r = parser
+        .run_inner(&["-v", "add", "--forse", "bpaf"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+no such flag: `--forse`
+
+    -v add --forse bpaf
+           ^^^^^^^
+
+help: did you mean `--force`?
+
+usage: add [-f] NAME";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn multiple_errors() {
+    let verbose = short('v').long("verbose").switch();
+    let jobs = long("jobs").argument::<usize>("N").fallback(1);
+    let name = long("name").argument::<String>("NAME");
+    let parser = construct!(verbose, jobs, name).to_options().max_errors(5);
+
+    let r = parser
+        .run_inner(&["--verbos", "--jbos", "2"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+3 problems found:
+  - expected `--name=NAME`, pass `--help` for usage information
+  - no such flag: `--verbos`, did you mean `--verbose`?
+  - no such flag: `--jbos`, did you mean `--jobs`?
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--name", "x", "--jobs", "x", "--jobs", "3"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+2 problems found:
+  - couldn't parse `x`: invalid digit found in string
+  - argument `--jobs` cannot be used multiple times in this context
+";
+    assert_eq!(r, expected);
+
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `--name=NAME`, pass `--help` for usage information"
+    );
+
+    let parser = parser.max_errors(2);
+    let r = parser
+        .run_inner(&["--verbos", "--jbos", "2"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+3 problems found:
+  - expected `--name=NAME`, pass `--help` for usage information
+  - no such flag: `--verbos`, did you mean `--verbose`?
+  - and 1 more problem
+";
+    assert_eq!(r, expected);
+
+    let parser = parser.max_errors(1);
+    let r = parser
+        .run_inner(&["--verbos", "--jbos", "2"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "no such flag: `--verbos`, did you mean `--verbose`?");
+}
+
+#[test]
+fn multiple_errors_handled_failures() {
+    #[derive(Debug, Clone, PartialEq)]
+    enum Mode {
+        Number(usize, bool),
+        Name(String),
+    }
+    let number = short('n').argument::<usize>("N");
+    let force = short('f').switch();
+    let number = construct!(Mode::Number(number, force));
+    let name = short('s').argument::<String>("S");
+    let name = construct!(Mode::Name(name));
+    let mode = construct!([number, name]);
+    let x = short('x').switch();
+    let parser = construct!(mode, x).to_options().max_errors(5);
+
+    let r = parser.run_inner(&["-s", "q", "-x"]).unwrap();
+    assert_eq!(r, (Mode::Name("q".to_owned()), true));
+
+    let r = parser
+        .run_inner(&["-n", "q", "-y"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+2 problems found:
+  - couldn't parse `q`: invalid digit found in string
+  - `-y` is not expected in this context
+";
+    assert_eq!(r, expected);
+
+    let a = short('a').argument::<usize>("A");
+    let b = short('b').argument::<usize>("B");
+    let parser = construct!(a, b).optional().to_options().max_errors(5);
+
+    let r = parser.run_inner(&[]).unwrap();
+    assert_eq!(r, None);
+
+    let r = parser.run_inner(&["-b", "1"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "expected `-a=A`, pass `--help` for usage information");
+}
+
+#[test]
+fn multiple_errors_diagnostic() {
+    let a = short('a').argument::<usize>("A");
+    let b = short('b').argument::<usize>("B");
+    let parser = construct!(a, b)
+        .to_options()
+        .max_errors(5)
+        .error_style(ErrorStyle::Diagnostic);
+
+    let r = parser
+        .run_inner(&["-a", "x", "-b", "y", "-c"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+couldn't parse `x`: invalid digit found in string
+
+    -a x -b y -c
+       ^
+
+couldn't parse `y`: invalid digit found in string
+
+    -a x -b y -c
+            ^
+
+`-c` is not expected in this context
+
+    -a x -b y -c
+              ^^
+
+usage: -a=A -b=B";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn multiple_errors_subcommand() {
+    let jobs = long("jobs").argument::<usize>("N");
+    let name = positional::<String>("NAME");
+    let build = construct!(jobs, name).to_options().command("build");
+    let verbose = short('v').switch();
+    let parser = construct!(verbose, build).to_options().max_errors(5);
+
+    let r = parser
+        .run_inner(&["build", "--jobs", "x", "-q"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+3 problems found:
+  - couldn't parse `x`: invalid digit found in string
+  - expected `NAME`, pass `--help` for usage information
+  - `-q` is not expected in this context
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn multiple_errors_subcommand_merged() {
+    let jobs = long("jobs").argument::<usize>("N");
+    let name = positional::<String>("NAME");
+    let build = construct!(jobs, name).to_options().command("build");
+    let user = long("user").argument::<String>("USER");
+    let parser = construct!(user, build).to_options().max_errors(5);
+
+    let r = parser
+        .run_inner(&["build", "--jobs", "x", "-q"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+4 problems found:
+  - expected `--user=USER`, pass `--help` for usage information
+  - couldn't parse `x`: invalid digit found in string
+  - expected `NAME`, pass `--help` for usage information
+  - `-q` is not expected in this context
+";
+    assert_eq!(r, expected);
+
+    let parser = parser.max_errors(2).error_style(ErrorStyle::Diagnostic);
+    let r = parser
+        .run_inner(&["build", "--jobs", "x", "-q"])
+        .unwrap_err()
+        .unwrap_stderr();
+    let expected = "\
+expected `--user=USER`, pass `--help` for usage information
+
+couldn't parse `x`: invalid digit found in string
+
+    build --jobs x -q
+                 ^
+
+and 2 more problems
+
+usage: --user=USER COMMAND ...";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn env_typo_warnings() {
+    std::env::set_var("BPAF_ENV_TEST_VERBOS", "1");
+    std::env::set_var("BPAF_ENV_TEST_TARGTE", "x86");
+    std::env::set_var("BPAF_ENV_TEST_SOMETHING_ELSE", "1");
+    std::env::set_var("BPAF_ENV_TEST_VERBOSE", "1");
+    let verbose = long("verbose").env("BPAF_ENV_TEST_VERBOSE").switch();
+    let target = long("target")
+        .env("BPAF_ENV_TEST_TARGET")
+        .argument::<String>("T")
+        .to_options()
+        .command("build");
+    let parser = construct!(verbose, target).to_options();
+    assert!(parser.env_warnings().is_empty());
+
+    let parser = parser.env_prefix("BPAF_ENV_TEST_");
+    assert_eq!(
+        parser.env_warnings(),
+        [
+            "environment variable `BPAF_ENV_TEST_TARGTE` is not used, did you mean `BPAF_ENV_TEST_TARGET`?",
+            "environment variable `BPAF_ENV_TEST_VERBOS` is not used, did you mean `BPAF_ENV_TEST_VERBOSE`?",
+        ]
+    );
+}
+
+#[test]
+fn guard_with_value_in_message() {
+    let jobs = long("jobs").argument::<usize>("N").guard_with(
+        |n| *n > 0,
+        |_| {
+            let mut doc = Doc::default();
+            doc.text("expected ");
+            doc.doc(&Doc::from(&[("N", Style::Metavar)]));
+            doc.text(" to be at least ");
+            doc.literal("1");
+            doc
+        },
+    );
+    let file = positional::<String>("FILE").guard_with(
+        |f| f.ends_with(".toml"),
+        |f| format!("{} is not a toml file", f),
+    );
+    let parser = construct!(jobs, file).to_options();
+
+    let r = parser
+        .run_inner(&["--jobs", "0", "a.toml"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`0`: expected N to be at least 1");
+
+    let r = parser
+        .run_inner(&["--jobs", "2", "a.json"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`a.json`: a.json is not a toml file");
+
+    let r = parser.run_inner(&["--jobs", "2", "a.toml"]).unwrap();
+    assert_eq!(r, (2, "a.toml".to_owned()));
+}
+
+#[cfg(feature = "docgen")]
+#[test]
+fn parse_styled_keeps_styles() {
+    fn level(input: String) -> Result<u8, &'static [(&'static str, Style); 4]> {
+        match input.as_str() {
+            "low" => Ok(1),
+            "high" => Ok(2),
+            _ => Err(&[
+                ("expected ", Style::Text),
+                ("low", Style::Literal),
+                (" or ", Style::Text),
+                ("high", Style::Literal),
+            ]),
+        }
+    }
+    let parser = long("level")
+        .argument::<String>("LEVEL")
+        .parse_styled(level)
+        .to_options();
+
+    let r = parser.run_inner(&["--level", "mid"]).unwrap_err();
+    let doc = match r {
+        ParseFailure::Stderr(doc) => doc,
+        _ => panic!("expected a parse error"),
+    };
+    assert_eq!(
+        doc.monochrome(true),
+        "couldn't parse `mid`: expected low or high"
+    );
+    assert_eq!(
+        doc.render_markdown(false),
+        "couldn't parse **mid**: expected **`low`** or **`high`**"
+    );
+}
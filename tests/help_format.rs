@@ -606,3 +606,276 @@ fn help_and_version_newline() {
         .unwrap_stdout();
     assert_eq!(r, "Version: 1\n");
 }
+
+#[test]
+fn derive_markdown_in_doc_comments() {
+    #[derive(Debug, Clone, Bpaf)]
+    #[bpaf(options)]
+    #[allow(dead_code)]
+    struct Options {
+        /// Read config from <FILE>, see `--format`
+        config: String,
+        /// Output format, one of:
+        /// - `json`
+        /// - `yaml`
+        ///
+        /// ```
+        /// app --format json
+        /// ```
+        format: String,
+    }
+
+    let r = options()
+        .run_inner(&["--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: --config=ARG --format=ARG
+
+Available options:
+        --config=ARG  Read config from FILE, see --format
+        --format=ARG  Output format, one of:
+                        - json
+                        - yaml
+    -h, --help        Prints help information
+";
+    assert_eq!(r, expected);
+
+    let r = options()
+        .run_inner(&["--help", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(r.contains(
+        "                        - yaml\n\n                          app --format json\n\n"
+    ));
+}
+
+#[test]
+fn wide_characters_in_help() {
+    let a = long("名前")
+        .help("名前を指定する")
+        .argument::<String>("名前");
+    let b = long("emoji").help("🦀 crab").switch();
+    let c = long("text")
+        .help("これはとても長い説明文で、一行に収まらないので適切な位置で折り返す必要があります。")
+        .switch();
+    let parser = construct!(a, b, c).to_options();
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: --名前=<名前> [--emoji] [--text]
+
+Available options:
+        --名前=<名前>  名前を指定する
+        --emoji        🦀 crab
+        --text         これはとても長い説明文で、一行に収まらないので適切な位置で折り返す必要がありま
+                       す。
+    -h, --help         Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn help_layout_options() {
+    #[derive(Debug, Clone, Bpaf)]
+    #[allow(dead_code)]
+    struct Net {
+        /// Server to connect to
+        #[bpaf(argument("HOST"), heading("Network:"))]
+        host: String,
+        /// Port to use
+        #[bpaf(heading("Network:"))]
+        port: u16,
+    }
+
+    #[derive(Debug, Clone, Bpaf)]
+    #[bpaf(options)]
+    #[allow(dead_code)]
+    enum Opts {
+        Run {
+            /// Verbose output
+            #[bpaf(short, long)]
+            verbose: bool,
+            #[bpaf(external(net), group_help("Connection:"))]
+            net: Net,
+            /// Timeout in seconds
+            #[bpaf(heading("Network:"))]
+            timeout: u32,
+            /// Use colors
+            color: bool,
+            /// Input file
+            #[bpaf(positional("FILE"))]
+            file: String,
+        },
+    }
+
+    let r = opts()
+        .help_order(HelpOrder::Alphabetical)
+        .section_title(HelpSection::Options, "Flags:")
+        .section_title(HelpSection::Positionals, "Arguments:")
+        .run_inner(&["--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: [-v] --host=HOST --port=ARG --timeout=ARG [--color] FILE
+
+Network:
+        --host=HOST    Server to connect to
+        --port=ARG     Port to use
+        --timeout=ARG  Timeout in seconds
+
+Arguments:
+    FILE               Input file
+
+Flags:
+        --color        Use colors
+    -h, --help         Prints help information
+    -v, --verbose      Verbose output
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn help_layout_commands() {
+    let build = pure(())
+        .to_options()
+        .descr("Build the project")
+        .command("build");
+    let audit = pure(())
+        .to_options()
+        .descr("Audit dependencies")
+        .command("audit")
+        .heading("Maintenance:");
+    let check = pure(())
+        .to_options()
+        .descr("Check the project")
+        .command("check");
+    let clean = pure(())
+        .to_options()
+        .descr("Remove build artifacts")
+        .command("clean")
+        .heading("Maintenance:");
+    let parser = construct!([build, audit, check, clean])
+        .to_options()
+        .help_order(HelpOrder::Alphabetical)
+        .section_title(HelpSection::Commands, "Subcommands:");
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: COMMAND ...
+
+Maintenance:
+    audit       Audit dependencies
+    clean       Remove build artifacts
+
+Available options:
+    -h, --help  Prints help information
+
+Subcommands:
+    build       Build the project
+    check       Check the project
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn help_layout_two_line() {
+    let retries = long("maximum-number-of-retries")
+        .help("Give up after that many attempts, this help is long enough to wrap on the next line for sure")
+        .argument::<usize>("N");
+    let verbose = short('v').help("Verbose output").switch();
+    let parser = construct!(retries, verbose)
+        .to_options()
+        .help_columns(HelpColumns::TwoLine);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: --maximum-number-of-retries=N [-v]
+
+Available options:
+        --maximum-number-of-retries=N
+                Give up after that many attempts, this help is long enough to wrap on the next line for
+                sure
+    -v          Verbose output
+    -h, --help  Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn compact_usage() {
+    let verbose = short('v').help("Verbose output").switch();
+    let jobs = short('j')
+        .help("Number of jobs")
+        .argument::<usize>("N")
+        .many();
+    let level = long("level")
+        .help("Optimization level")
+        .argument::<u8>("L")
+        .fallback(2)
+        .display_fallback();
+    let target = long("target")
+        .help("Build target")
+        .argument::<String>("TARGET");
+    let debug = long("debug").req_flag(true);
+    let release = long("release").req_flag(false);
+    let mode = construct!([debug, release]).optional();
+    let file = positional::<String>("FILE").many();
+    let parser = construct!(verbose, jobs, target, level, mode, file)
+        .to_options()
+        .usage_style(UsageStyle::Compact);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert!(r.starts_with("Usage: [OPTIONS] --target=TARGET [FILE]...\n"));
+
+    let sub = construct!(verbose2(), target2())
+        .to_options()
+        .descr("Build the project")
+        .command("build");
+    let parser = construct!(verbose2(), sub)
+        .to_options()
+        .usage_style(UsageStyle::Compact);
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert!(r.starts_with("Usage: [OPTIONS] COMMAND ...\n"));
+    let r = parser
+        .run_inner(&["build", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(r.starts_with("Build the project\n\nUsage: build [OPTIONS] --target=TARGET\n"));
+}
+
+fn verbose2() -> impl Parser<bool> {
+    short('v').help("Verbose output").switch()
+}
+
+fn target2() -> impl Parser<String> {
+    long("target")
+        .help("Build target")
+        .argument::<String>("TARGET")
+}
+
+#[test]
+fn group_inside_heading() {
+    let a = short('a').help("A flag").switch();
+    let b = short('b').help("B flag").switch();
+    let c = short('c').help("C flag").switch();
+    let d = short('d').help("D flag").switch();
+    let ab = construct!(a, b).group_help("Group:");
+    let net = construct!(ab, c).heading("Net:");
+    let parser = construct!(net, d).to_options();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-a] [-b] [-c] [-d]
+
+Net:
+    -c          C flag
+  Group:
+    -a          A flag
+    -b          B flag
+
+Available options:
+    -d          D flag
+    -h, --help  Prints help information
+";
+    assert_eq!(r, expected);
+}
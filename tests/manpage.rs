@@ -1,6 +1,6 @@
 use bpaf::*;
 
-use bpaf::doc::Section;
+use bpaf::doc::{Doc, Section};
 
 fn write_updated(new_val: &str, path: impl AsRef<std::path::Path>) -> std::io::Result<bool> {
     use std::io::Read;
@@ -9,6 +9,7 @@ fn write_updated(new_val: &str, path: impl AsRef<std::path::Path>) -> std::io::R
         .write(true)
         .read(true)
         .create(true)
+        .truncate(false)
         .open(path)?;
     let mut current_val = String::new();
     file.read_to_string(&mut current_val)?;
@@ -128,3 +129,193 @@ fn very_nested() {
     #[cfg(unix)]
     assert!(write_updated(&roff, "tests/very_nested.1").unwrap());
 }
+
+#[test]
+fn per_command_pages() {
+    let deploy = short('f')
+        .help("Deploy even if checks fail")
+        .switch()
+        .to_options()
+        .descr("Deploy the build")
+        .command("deploy");
+    let build = construct!(deploy)
+        .to_options()
+        .descr("Build the project")
+        .header("Builds everything in the workspace")
+        .command("build");
+    let pages = build.to_options().descr("Project manager").render_manpages(
+        "app",
+        Section::General,
+        Some("Aug 2022"),
+        None,
+        None,
+    );
+
+    let names = pages.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(names, ["app-build-deploy.1", "app-build.1", "app.1"]);
+
+    let expected = r#".ie \n(.g .ds Aq \(aq
+.el .ds Aq '
+.TH app-build 1 Aug\ 2022 - ""
+.SH NAME
+\fRapp\-build \- \fP\fRBuild the project\fP
+.SH SYNOPSIS
+\fBapp\fP\fR \fP\fBbuild\fP\fR \fP\fICOMMAND ...\fP
+.SH DESCRIPTION
+.PP
+\fRBuild the project\fP
+.PP
+\fRBuilds everything in the workspace\fP
+.PP
+.SS AVAILABLE\ OPTIONS:
+.TP
+\fB\-h\fP\fR, \fP\fB\-\-help\fP
+\fRPrints help information\fP
+.PP
+.PP
+.SS AVAILABLE\ COMMANDS:
+.TP
+\fBdeploy\fP
+\fRDeploy the build\fP
+.PP
+.SH EXIT\ STATUS
+.TP
+\fB0\fP
+\fRSuccessful run, also after printing help or version information\fP
+.PP
+.TP
+\fB1\fP
+\fRFailed to parse the command line\fP
+.PP
+.SH SEE\ ALSO
+.PP
+\fBapp\fP\fR(1), \fP\fBapp\-build\-deploy\fP\fR(1)\fP"#;
+    assert_eq!(pages["app-build.1"], expected);
+
+    let deploy = &pages["app-build-deploy.1"];
+    assert!(deploy.contains("Deploy even if checks fail"));
+    assert!(deploy.ends_with(".SH SEE\\ ALSO\n.PP\n\\fBapp\\-build\\fP\\fR(1)\\fP"));
+}
+
+#[test]
+fn custom_sections() {
+    let user = long("user")
+        .env("APP_USER")
+        .help("Log in as this user")
+        .argument::<String>("USER");
+    let options = construct!(user)
+        .to_options()
+        .descr("I am a program and I do things")
+        .section("SEE ALSO", "ssh(1)")
+        .section("FILES", "~/.config/app.toml")
+        .section("AUTHORS", "Written by the app team");
+    let roff = options.render_manpage("custom", Section::General, Some("Aug 2022"), None, None);
+
+    #[cfg(unix)]
+    assert!(write_updated(&roff, "tests/custom.1").unwrap());
+}
+
+#[test]
+fn custom_sections_replace_generated() {
+    let user = || {
+        long("user")
+            .env("APP_USER")
+            .help("Log in as this user")
+            .argument::<String>("USER")
+    };
+    let options = user()
+        .to_options()
+        .section("Exit Status", "Always 0")
+        .section("FILES", "~/.config/app.toml");
+    let roff = options.render_manpage("app", Section::General, None, None, None);
+    let headers = roff
+        .lines()
+        .filter(|l| l.starts_with(".SH"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        headers,
+        [
+            ".SH SYNOPSIS",
+            ".SH EXIT\\ STATUS",
+            ".SH ENVIRONMENT",
+            ".SH FILES"
+        ]
+    );
+    assert!(!roff.contains("Failed to parse the command line"));
+
+    let options = options.section("ENVIRONMENT", "None of them");
+    let roff = options.render_manpage("app", Section::General, None, None, None);
+    assert!(
+        roff.contains(".SH ENVIRONMENT\n.PP\n\\fRNone of them\\fP"),
+        "{}",
+        roff
+    );
+    assert_eq!(roff.matches(".SH ENVIRONMENT").count(), 1, "{}", roff);
+
+    let options = user()
+        .to_options()
+        .section("FILES", "~/.config/app.toml")
+        .generated_sections(false);
+    let roff = options.render_manpage("app", Section::General, None, None, None);
+    assert!(!roff.contains("EXIT"), "{}", roff);
+    assert!(!roff.contains("ENVIRONMENT"), "{}", roff);
+    assert!(roff.contains(".SH FILES"), "{}", roff);
+}
+
+#[test]
+fn rich_blocks() {
+    let mut footer = Doc::default();
+    footer.bullet_list(["json", "yaml"]);
+    footer.code_block("app --format json");
+    footer.link("Manual", "https://example.com");
+    let roff = short('v')
+        .switch()
+        .to_options()
+        .footer(footer)
+        .render_manpage("app", Section::General, None, None, None);
+
+    let expected = r#".IP \(bu 2
+\fRjson\fP
+.IP \(bu 2
+\fRyaml\fP
+.PP
+.PP
+.EX
+\fRapp \-\-format json\fP
+.EE
+.UR https://example.com
+\fRManual\fP
+.UE
+"#;
+    assert!(roff.contains(expected), "{}", roff);
+}
+
+#[test]
+fn per_command_pages_list_parent_globals() {
+    let verbose = short('v')
+        .long("verbose")
+        .help("Print more output")
+        .global()
+        .switch();
+    let deploy = pure(())
+        .to_options()
+        .descr("Deploy the build")
+        .command("deploy");
+    let build = construct!(deploy)
+        .to_options()
+        .descr("Build the project")
+        .command("build");
+    let pages = construct!(verbose, build).to_options().render_manpages(
+        "app",
+        Section::General,
+        None,
+        None,
+        None,
+    );
+
+    for page in ["app-build.1", "app-build-deploy.1"] {
+        let page = &pages[page];
+        assert!(page.contains("GLOBAL\\ OPTIONS:"), "{}", page);
+        assert!(page.contains("Print more output"), "{}", page);
+    }
+}
@@ -10,6 +10,7 @@ fn write_updated(new_val: &str, path: impl AsRef<std::path::Path>) -> std::io::R
         .write(true)
         .read(true)
         .create(true)
+        .truncate(false)
         .open(path)?;
     let mut current_val = String::new();
     file.read_to_string(&mut current_val)?;
@@ -112,6 +113,10 @@ Even More Beta
 **Available options:**\n- **`-h`**, **`--help`** &mdash; \n  Prints help information
 
 
+## EXIT STATUS
+- **`0`** &mdash; \n  Successful run, also after printing help or version information
+- **`1`** &mdash; \n  Failed to parse the command line
+
 ";
     assert_eq!(r, expected);
 }
@@ -133,6 +138,10 @@ fn multi_line_help() {
 - **`-h`**, **`--help`** &mdash; \n  Prints help information
 
 
+## EXIT STATUS
+- **`0`** &mdash; \n  Successful run, also after printing help or version information
+- **`1`** &mdash; \n  Failed to parse the command line
+
 ";
     assert_eq!(r, expected);
 }
@@ -152,7 +161,7 @@ fn no_help() {
 
 **Available options:**
 - **`-a`** &mdash; \n  help
-- **`-b`**\n- **`-h`**, **`--help`** &mdash; \n  Prints help information\n\n\n";
+- **`-b`**\n- **`-h`**, **`--help`** &mdash; \n  Prints help information\n\n\n## EXIT STATUS\n- **`0`** &mdash; \n  Successful run, also after printing help or version information\n- **`1`** &mdash; \n  Failed to parse the command line\n\n";
 
     assert_eq!(r, expected);
 }
@@ -200,7 +209,7 @@ Available options:
   code
   ```
 
-- **`-h`**, **`--help`** &mdash; \n  Prints help information\n\n\n";
+- **`-h`**, **`--help`** &mdash; \n  Prints help information\n\n\n## EXIT STATUS\n- **`0`** &mdash; \n  Successful run, also after printing help or version information\n- **`1`** &mdash; \n  Failed to parse the command line\n\n";
 
     assert_eq!(r, expected);
 }
@@ -226,11 +235,11 @@ Usage: [--verbose]
 
 Available options:
         --verbose  Verbose help
-                   ```text
-                   block
-                       of
-                       code
-                   ```
+
+                       block
+                           of
+                           code
+
     -h, --help     Prints help information
 ";
     assert_eq!(r, expected);
@@ -244,13 +253,273 @@ Available options:
 **Available options:**
 - **`    --verbose`** &mdash; \n  Verbose help
 
-  \n  ```text
+  ```text
   block
       of
       code
   ```
 
-- **`-h`**, **`--help`** &mdash; \n  Prints help information\n\n\n";
+- **`-h`**, **`--help`** &mdash; \n  Prints help information\n\n\n## EXIT STATUS\n- **`0`** &mdash; \n  Successful run, also after printing help or version information\n- **`1`** &mdash; \n  Failed to parse the command line\n\n";
 
     assert_eq!(r, expected);
 }
+
+#[test]
+fn codeblock_language_is_kept() {
+    #[derive(Bpaf, Clone, Debug)]
+    #[bpaf(options)]
+    struct Options {
+        /// Verbose help
+        ///
+        /// ```sh
+        /// ml --verbose
+        /// ```
+        verbose: bool,
+    }
+
+    let r = options().render_markdown("ml");
+    assert!(r.contains("  Verbose help\n\n  ```sh\n  ml --verbose\n  ```\n"));
+
+    let r = options().render_html("ml");
+    assert!(r.contains("<pre class=\"language-sh\">ml --verbose</pre>"));
+
+    let r = options().run_inner(&["-hh"]).unwrap_err().unwrap_stdout();
+    assert!(r.contains("Verbose help\n\n                       ml --verbose\n\n"));
+}
+
+#[test]
+fn examples_section() {
+    let opts = short('a')
+        .help("help")
+        .switch()
+        .to_options()
+        .example("-a", "Run with a");
+    let r = opts.render_markdown("ex");
+    assert!(r.ends_with("## EXAMPLES\n- **`ex`** **`-a`** &mdash; \n  Run with a\n\n"));
+}
+
+#[test]
+fn markdown_pages() {
+    let force = short('f')
+        .long("force")
+        .help("Deploy even if checks fail")
+        .switch();
+    let deploy = construct!(force)
+        .to_options()
+        .descr("Deploy the build")
+        .command("deploy")
+        .help("Deploy the build");
+    let build = construct!(deploy)
+        .to_options()
+        .descr("Build the project")
+        .command("build")
+        .help("Build the project");
+    let pages = build.to_options().render_markdown_pages("app");
+
+    let names = pages.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["build/deploy/index.md", "build/index.md", "index.md"]
+    );
+
+    let index = &pages["index.md"];
+    assert!(index.contains("  * [`app build deploy`](build/deploy/index.md)\n"));
+    assert!(index.contains(
+        "- <a id=\"cmd-build\"></a>[**`build`**](build/index.md) &mdash; \n  Build the project\n"
+    ));
+    assert!(pages["build/index.md"].contains("[**`deploy`**](deploy/index.md)"));
+
+    let expected = "\
+[app](../../index.md) › [build](../index.md) › deploy
+
+
+# app build deploy
+
+Deploy the build
+
+**Usage**: **`app`** **`build`** **`deploy`** \\[**`-f`**\\]
+
+**Available options:**
+- <a id=\"flag-force\"></a>**`-f`**, **`--force`** &mdash; \n  Deploy even if checks fail
+- <a id=\"flag-help\"></a>**`-h`**, **`--help`** &mdash; \n  Prints help information
+
+
+";
+    assert_eq!(pages["build/deploy/index.md"], expected);
+}
+
+#[test]
+fn markdown_page_files_are_unique() {
+    let verbose = short('v').help("Print more output").global().switch();
+    let index = pure(()).to_options().command("index");
+    let a_b = pure(()).to_options().command("a-b");
+    let dots = pure(()).to_options().command("..");
+    let b = pure(()).to_options().command("b");
+    let a = construct!(b).to_options().command("a");
+    let parser = construct!(verbose, index, a_b, dots, a).to_options();
+    let pages = parser.render_markdown_pages("app");
+
+    let names = pages.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "_2E_2E/index.md",
+            "a-b/index.md",
+            "a/b/index.md",
+            "a/index.md",
+            "index.md",
+            "index/index.md"
+        ]
+    );
+    assert!(pages["a/b/index.md"].starts_with("[app](../../index.md) › [a](../index.md) › b"));
+    assert!(pages["a/b/index.md"].contains("Print more output"));
+}
+
+#[test]
+fn markdown_page_anchors_are_unique() {
+    let short = short('v').help("Short").switch();
+    let long = long("v").help("Long").switch();
+    let help = pure(()).to_options().descr("Help command").command("help");
+    let parser = construct!(short, long, help).to_options();
+    let pages = parser.render_markdown_pages("app");
+
+    let index = &pages["index.md"];
+    assert!(index.contains("<a id=\"flag-v\"></a>**`-v`**"));
+    assert!(index.contains("<a id=\"flag-v-2\"></a>**`    --v`**"));
+    assert!(index.contains("<a id=\"flag-help\"></a>**`-h`**, **`--help`**"));
+    assert!(index.contains("<a id=\"cmd-help\"></a>[**`help`**](help/index.md)"));
+}
+
+#[test]
+fn doc_regions() {
+    let deploy = short('f')
+        .help("Deploy even if checks fail")
+        .switch()
+        .to_options()
+        .descr("Deploy the build")
+        .command("deploy");
+    let parser = construct!(deploy).to_options().descr("Project manager");
+
+    let dir = std::env::temp_dir().join(format!("bpaf_doc_regions_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let readme = dir.join("README.md");
+    let stale = "# App\n\n<!-- bpaf:usage app deploy -->\nUsage: old\n<!-- bpaf:end -->\n\n<!-- bpaf:help app deploy -->\n<!-- bpaf:end -->\nThe end\n";
+    std::fs::write(&readme, stale).unwrap();
+
+    let err = parser.check_doc_regions(&readme).unwrap_err();
+    let expected = format!(
+        "{0}: region `usage app deploy` is out of date
+-Usage: old
++```text
++Usage: app deploy [-f]
++```
+{0}: region `help app deploy` is out of date
++```text
++Deploy the build
++
++Usage: app deploy [-f]
++
++Available options:
++    -f          Deploy even if checks fail
++    -h, --help  Prints help information
++```
+",
+        readme.display()
+    );
+    assert_eq!(err, expected);
+    assert_eq!(std::fs::read_to_string(&readme).unwrap(), stale);
+
+    assert!(parser.update_doc_regions(&readme).unwrap());
+    assert!(parser.check_doc_regions(&readme).is_ok());
+    assert!(!parser.update_doc_regions(&readme).unwrap());
+    let updated = std::fs::read_to_string(&readme).unwrap();
+    assert!(updated.starts_with("# App\n\n<!-- bpaf:usage app deploy -->\n```text\n"));
+    assert!(updated.ends_with("```\n<!-- bpaf:end -->\nThe end\n"));
+
+    std::fs::write(&readme, "<!-- bpaf:markdown app -->\n<!-- bpaf:end -->\n").unwrap();
+    assert!(parser.update_doc_regions(&readme).unwrap());
+    let updated = std::fs::read_to_string(&readme).unwrap();
+    assert!(updated.contains("Project manager"));
+    assert!(updated.contains("Deploy even if checks fail"));
+
+    std::fs::write(&readme, "<!-- bpaf:help app build -->\n<!-- bpaf:end -->\n").unwrap();
+    let err = parser.check_doc_regions(&readme).unwrap_err();
+    assert!(err.ends_with("region `help app build`: no such command `build`"));
+
+    std::fs::write(&readme, "<!-- bpaf:help app -->\n").unwrap();
+    let err = parser.update_doc_regions(&readme).unwrap_err();
+    assert_eq!(
+        err,
+        format!("{}: region `help app` is not closed", readme.display())
+    );
+
+    let example = "````markdown\n<!-- bpaf:help app -->\n```\n<!-- bpaf:end -->\n````\n";
+    std::fs::write(&readme, example).unwrap();
+    assert!(!parser.update_doc_regions(&readme).unwrap());
+    assert!(parser.check_doc_regions(&readme).is_ok());
+
+    // generated content follows line endings of the file
+    let crlf = "# App\r\n<!-- bpaf:usage app deploy -->\r\n<!-- bpaf:end -->\r\nThe end\r\n";
+    std::fs::write(&readme, crlf).unwrap();
+    assert!(parser.check_doc_regions(&readme).is_err());
+    assert!(parser.update_doc_regions(&readme).unwrap());
+    assert!(parser.check_doc_regions(&readme).is_ok());
+    assert!(!parser.update_doc_regions(&readme).unwrap());
+    let updated = std::fs::read_to_string(&readme).unwrap();
+    assert_eq!(
+        updated,
+        "# App\r\n<!-- bpaf:usage app deploy -->\r\n```text\r\nUsage: app deploy [-f]\r\n```\r\n<!-- bpaf:end -->\r\nThe end\r\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compact_usage() {
+    let verbose = short('v').help("Verbose output").switch();
+    let file = positional::<String>("FILE");
+    let parser = construct!(verbose, file)
+        .to_options()
+        .usage_style(UsageStyle::Compact);
+    let r = parser.render_markdown("app");
+    assert!(
+        r.contains("**Usage**: **`app`** \\[_`OPTIONS`_\\] _`FILE`_"),
+        "{}",
+        r
+    );
+    let r = parser.render_manpage("app", doc::Section::General, None, None, None);
+    assert!(
+        r.contains("\\fR[\\fP\\fIOPTIONS\\fP\\fR]\\fP\\fR \\fP\\fIFILE\\fP"),
+        "{}",
+        r
+    );
+}
+
+#[test]
+fn sections_for_items_under_headings() {
+    let host = long("host")
+        .env("APP_HOST")
+        .help("Server to connect to")
+        .argument::<String>("HOST")
+        .heading("Connection");
+    let force = long("force").help("Deploy even if checks fail").switch();
+    let deploy = construct!(force)
+        .to_options()
+        .descr("Deploy the build")
+        .command("deploy")
+        .heading("Server commands");
+    let parser = construct!(host, deploy).to_options();
+
+    let r = parser.render_markdown("app");
+    assert!(r.contains("# app deploy\n"), "{}", r);
+    assert!(r.contains("Deploy even if checks fail"), "{}", r);
+    assert!(r.contains("## ENVIRONMENT\n"), "{}", r);
+
+    let pages = parser.render_markdown_pages("app");
+    assert!(pages.contains_key("deploy/index.md"));
+
+    let roff = parser.render_manpage("app", doc::Section::General, None, None, None);
+    assert!(roff.contains(".SH APP\\ DEPLOY"), "{}", roff);
+    assert!(roff.contains(".SH ENVIRONMENT"), "{}", roff);
+    assert!(roff.contains("APP_HOST"), "{}", roff);
+}
@@ -370,3 +370,46 @@ fn quadruple_strict_many() {
     let parser = construct!([ab, df]).to_options();
     assert_usage(parser, "([-a] -- [B]... | [-d] -- [F]...)");
 }
+
+#[test]
+fn bounded_many() {
+    let parser = positional::<String>("FILE")
+        .many()
+        .at_least(2)
+        .at_most(5)
+        .to_options();
+    assert_usage(parser, "FILE{2,5}");
+
+    let parser = positional::<String>("FILE")
+        .many()
+        .at_least(3)
+        .at_most(3)
+        .to_options();
+    assert_usage(parser, "FILE{3}");
+
+    let parser = short('a')
+        .argument::<String>("A")
+        .many()
+        .at_least(2)
+        .to_options();
+    assert_usage(parser, "-a=A{2,}");
+
+    let parser = short('a')
+        .argument::<String>("A")
+        .many()
+        .at_most(2)
+        .to_options();
+    assert_usage(parser, "[-a=A]{,2}");
+}
+
+#[test]
+fn bounded_collect_group() {
+    let a = short('a').argument::<String>("A");
+    let b = short('b').argument::<String>("B");
+    let parser = construct!(a, b)
+        .collect::<Vec<_>>()
+        .at_least(1)
+        .at_most(2)
+        .to_options();
+    assert_usage(parser, "(-a=A -b=B){1,2}");
+}
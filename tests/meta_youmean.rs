@@ -307,3 +307,27 @@ fn pure_conflict() {
     let r = parser.run_inner(&["-a"]).unwrap();
     assert!(r);
 }
+
+#[test]
+fn suggest_case_and_underscore_typos() {
+    let dir = long("output-dir").argument::<String>("DIR");
+    let dim = long("output-dim").argument::<String>("DIM").optional();
+    let parser = construct!(dir, dim).to_options();
+
+    let r = parser
+        .run_inner(&["--Output_Dir", "tmp"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such flag: `--Output_Dir`, did you mean `--output-dir`?"
+    );
+
+    let build = pure(()).to_options().command("build");
+    let parser = construct!([build]).to_options();
+    let r = parser.run_inner(&["BUILD"]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `BUILD`, did you mean `build`?"
+    );
+}
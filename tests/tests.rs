@@ -1828,3 +1828,955 @@ fn flag_like_commands() {
         "Usage: --add -a\n\nAvailable options:\n    -a\n    -h, --help  Prints help information\n";
     assert_eq!(r, expected);
 }
+
+#[test]
+fn abbreviated_long_names() {
+    fn flags() -> impl Parser<(bool, bool, String)> {
+        let verbose = long("verbose").switch();
+        let verb = long("verb").switch();
+        let name = long("name").argument::<String>("NAME");
+        construct!(verbose, verb, name)
+    }
+    let parser = flags().to_options().abbreviations();
+
+    let r = parser.run_inner(&["--verbo", "--na", "bob"]).unwrap();
+    assert_eq!(r, (true, false, "bob".to_owned()));
+
+    // exact match wins over a longer name
+    let r = parser.run_inner(&["--verb", "--name=bob"]).unwrap();
+    assert_eq!(r, (false, true, "bob".to_owned()));
+
+    let r = parser.run_inner(&["--nam=bob"]).unwrap();
+    assert_eq!(r, (false, false, "bob".to_owned()));
+
+    // prefix matching is opt-in
+    let parser = flags().to_options();
+    let r = parser
+        .run_inner(&["--verbo", "--name", "bob"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "no such flag: `--verbo`, did you mean `--verb`?");
+}
+
+#[test]
+fn abbreviated_help_and_version() {
+    let parser = short('a')
+        .switch()
+        .to_options()
+        .version("1.0")
+        .abbreviations();
+
+    let r = parser.run_inner(&["--vers"]).unwrap_err().unwrap_stdout();
+    assert_eq!(r, "Version: 1.0\n");
+
+    let r = parser.run_inner(&["--he"]).unwrap_err().unwrap_stdout();
+    assert!(r.starts_with("Usage: "));
+}
+
+#[test]
+fn abbreviated_commands() {
+    let build = long("release")
+        .switch()
+        .to_options()
+        .command("build")
+        .map(|r| ("build", r));
+    let bench = long("release")
+        .switch()
+        .to_options()
+        .command("bench")
+        .map(|r| ("bench", r));
+    let check = pure(false)
+        .to_options()
+        .command("check")
+        .map(|r| ("check", r));
+    let parser = construct!([build, bench, check])
+        .to_options()
+        .abbreviations();
+
+    let r = parser.run_inner(&["bu", "--rel"]).unwrap();
+    assert_eq!(r, ("build", true));
+
+    let r = parser.run_inner(&["ben"]).unwrap();
+    assert_eq!(r, ("bench", false));
+
+    let r = parser.run_inner(&["c"]).unwrap();
+    assert_eq!(r, ("check", false));
+
+    let r = parser.run_inner(&["b"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`b` is ambiguous, could be `bench` or `build`");
+}
+
+#[test]
+fn relaxed_name_matching() {
+    fn parser() -> impl Parser<(bool, String)> {
+        let dry = long("dry-run").switch();
+        let dir = long("output-dir").argument::<String>("DIR");
+        construct!(dry, dir)
+    }
+
+    let p = parser().to_options().ignore_case();
+    let r = p.run_inner(&["--Dry-Run", "--OUTPUT-DIR=a"]).unwrap();
+    assert_eq!(r, (true, "a".to_owned()));
+    let r = p
+        .run_inner(&["--output_dir", "a"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such flag: `--output_dir`, did you mean `--output-dir`?"
+    );
+
+    let p = parser().to_options().underscores_as_dashes();
+    let r = p.run_inner(&["--dry_run", "--output_dir", "a"]).unwrap();
+    assert_eq!(r, (true, "a".to_owned()));
+
+    let p = parser()
+        .to_options()
+        .ignore_case()
+        .underscores_as_dashes()
+        .abbreviations();
+    let r = p.run_inner(&["--Dry", "--Output_D", "a"]).unwrap();
+    assert_eq!(r, (true, "a".to_owned()));
+
+    let r = p
+        .run_inner(&["--output-dir", "a", "--Output_Dir", "b"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "argument `--Output_Dir` cannot be used multiple times in this context"
+    );
+}
+
+#[test]
+fn relaxed_command_matching() {
+    let build = long("dry-run").switch().to_options().command("build_all");
+    let parser = construct!([build]).to_options();
+    let r = parser
+        .run_inner(&["Build-All"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `Build-All`, did you mean `build_all`?"
+    );
+
+    // settings propagate into nested parsers
+    let parser = parser.ignore_case().underscores_as_dashes();
+    let r = parser.run_inner(&["Build-All", "--DRY_RUN"]).unwrap();
+    assert!(r);
+}
+
+#[test]
+fn single_dash_names() {
+    let name = single_dash("name")
+        .help("File name pattern")
+        .argument::<String>("PATTERN")
+        .optional();
+    let n = short('n').switch();
+    let a = short('a').switch();
+    let empty = single_dash("empty").single_dash("e").switch();
+    let parser = construct!(name, n, a, empty).to_options();
+
+    // single dash names win over groups of short flags
+    let r = parser.run_inner(&["-name", "*.rs", "-na"]).unwrap();
+    assert_eq!(r, (Some("*.rs".to_owned()), true, true, false));
+
+    let r = parser.run_inner(&["-e"]).unwrap();
+    assert_eq!(r, (None, false, false, true));
+
+    let r = parser
+        .run_inner(&["--name", "x"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "no such flag: `--name`, did you mean `-name`?");
+
+    let r = parser
+        .run_inner(&["-nmae", "x"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "no such flag: `-nmae`, did you mean `-name`?");
+
+    let r = parser.run_inner(&["-name"]).unwrap_err().unwrap_stderr();
+    assert_eq!(r, "`-name` requires an argument `PATTERN`");
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-name PATTERN] [-n] [-a] [-empty]
+
+Available options:
+        -name PATTERN  File name pattern
+    -n
+    -a
+        -empty
+    -h, --help         Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn single_dash_toggles() {
+    let backing = single_dash("backing")
+        .help("Set backing status")
+        .toggle()
+        .fallback(true);
+    let xinerama = single_dash("xinerama").toggle();
+    let parser = construct!(backing, xinerama).to_options();
+
+    let r = parser.run_inner(&["+xinerama", "-backing"]).unwrap();
+    assert_eq!(r, (false, true));
+
+    let r = parser.run_inner(&["-xinerama"]).unwrap();
+    assert_eq!(r, (true, false));
+
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `(+|-)xinerama`, pass `--help` for usage information"
+    );
+
+    let r = parser
+        .run_inner(&["+xinerama", "+xinerama"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "argument `+xinerama` cannot be used multiple times in this context"
+    );
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [(+|-)backing] (+|-)xinerama
+
+Available options:
+        (+|-)backing   Set backing status
+        (+|-)xinerama
+    -h, --help         Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn single_dash_plus_without_toggle() {
+    let user = short('u').single_dash("user").argument::<String>("U");
+    let rest = positional::<String>("REST").many();
+    let parser = construct!(user, rest).to_options();
+
+    let r = parser.run_inner(&["-user", "a", "+user", "b"]).unwrap();
+    assert_eq!(
+        r,
+        ("a".to_owned(), vec!["+user".to_owned(), "b".to_owned()])
+    );
+
+    let r = parser.run_inner(&["+user", "-u", "b"]).unwrap();
+    assert_eq!(r, ("b".to_owned(), vec!["+user".to_owned()]));
+}
+
+#[test]
+fn slash_style_names() {
+    fn options(style: OptionStyle) -> OptionParser<(bool, String, Vec<String>)> {
+        let verbose = short('v').long("verbose").help("Be verbose").switch();
+        let out = long("out").help("Output file").argument::<String>("FILE");
+        let files = positional::<String>("FILE").many();
+        construct!(verbose, out, files)
+            .to_options()
+            .option_style(style)
+    }
+
+    let parser = options(OptionStyle::DashAndSlash);
+    let r = parser.run_inner(&["/v", "/out:a.txt", "/usr/bin"]).unwrap();
+    assert_eq!(r, (true, "a.txt".to_owned(), vec!["/usr/bin".to_owned()]));
+
+    let r = parser.run_inner(&["/verbose", "/out=a.txt"]).unwrap();
+    assert_eq!(r, (true, "a.txt".to_owned(), Vec::new()));
+
+    let r = parser.run_inner(&["--verbose", "/out", "a.txt"]).unwrap();
+    assert_eq!(r, (true, "a.txt".to_owned(), Vec::new()));
+
+    let r = parser
+        .run_inner(&["/out:a.txt", "/out:b.txt"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "argument `/out` cannot be used multiple times in this context"
+    );
+
+    let parser = options(OptionStyle::Slash);
+    let r = parser.run_inner(&["/v", "/out:a.txt", "-v"]).unwrap();
+    assert_eq!(r, (true, "a.txt".to_owned(), vec!["-v".to_owned()]));
+
+    let r = parser.run_inner(&[]).unwrap_err().unwrap_stderr();
+    assert_eq!(
+        r,
+        "expected `/out:FILE`, pass `/help` for usage information"
+    );
+
+    let r = parser.run_inner(&["/h"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [/v] /out:FILE [FILE]...
+
+Available options:
+    /v, /verbose   Be verbose
+        /out:FILE  Output file
+    /h, /help      Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn slash_style_hidden_aliases() {
+    let out = long("output").long("out").argument::<String>("FILE");
+    let parser = out.to_options().option_style(OptionStyle::Slash);
+
+    let r = parser.run_inner(&["/out", "x"]).unwrap();
+    assert_eq!(r, "x");
+
+    let r = parser.run_inner(&["/out:x"]).unwrap();
+    assert_eq!(r, "x");
+
+    let r = parser.run_inner(&["/output=x"]).unwrap();
+    assert_eq!(r, "x");
+}
+
+#[test]
+fn slash_style_relaxed_names() {
+    fn options() -> OptionParser<(bool, String)> {
+        let verbose = short('v').long("verbose").switch();
+        let out = long("out-file").argument::<String>("FILE");
+        construct!(verbose, out)
+            .to_options()
+            .option_style(OptionStyle::Slash)
+    }
+
+    let parser = options().ignore_case();
+    let r = parser.run_inner(&["/VERBOSE", "/OUT-FILE:x"]).unwrap();
+    assert_eq!(r, (true, "x".to_owned()));
+
+    let parser = options().abbreviations();
+    let r = parser.run_inner(&["/verb", "/ou:x"]).unwrap();
+    assert_eq!(r, (true, "x".to_owned()));
+
+    let parser = options().underscores_as_dashes();
+    let r = parser.run_inner(&["/out_file=x"]).unwrap();
+    assert_eq!(r, (false, "x".to_owned()));
+
+    let parser = options().abbreviations().ignore_case();
+    let r = parser.run_inner(&["/VERB", "/OU", "x"]).unwrap();
+    assert_eq!(r, (true, "x".to_owned()));
+
+    // without relaxed matching those are positional items
+    let r = options()
+        .run_inner(&["/verb", "/ou:x"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `/verb`, did you mean `/verbose`?"
+    );
+}
+
+#[test]
+fn options_first_mode() {
+    let verbose = short('v').long("verbose").help("Be verbose").switch();
+    let user = short('u')
+        .help("Run as user")
+        .argument::<String>("USER")
+        .optional();
+    let cmd = positional::<String>("CMD").help("Command to run").many();
+    let parser = construct!(verbose, user, cmd).to_options().options_first();
+
+    let r = parser
+        .run_inner(&["-v", "-u", "root", "ls", "-v", "--", "--help"])
+        .unwrap();
+    assert_eq!(
+        r,
+        (
+            true,
+            Some("root".to_owned()),
+            vec![
+                "ls".to_owned(),
+                "-v".to_owned(),
+                "--".to_owned(),
+                "--help".to_owned()
+            ]
+        )
+    );
+
+    let r = parser.run_inner(&["ls", "-u", "root"]).unwrap();
+    assert_eq!(
+        r,
+        (
+            false,
+            None,
+            vec!["ls".to_owned(), "-u".to_owned(), "root".to_owned()]
+        )
+    );
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-v] [-u=USER] [CMD]...
+
+Options are accepted only before the first positional item, everything after it is treated as positional
+
+Available positional items:
+    CMD            Command to run
+
+Available options:
+    -v, --verbose  Be verbose
+    -u=USER        Run as user
+    -h, --help     Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn options_first_commands() {
+    let verbose = short('v').switch();
+    let args = positional::<String>("ARG").many();
+    let run = construct!(verbose, args).to_options().command("run");
+    let verbose = short('v').switch();
+    let parser = construct!(verbose, run).to_options().options_first();
+
+    let r = parser.run_inner(&["-v", "run", "-v", "a", "-v"]).unwrap();
+    assert_eq!(r, (true, (true, vec!["a".to_owned(), "-v".to_owned()])));
+}
+
+#[test]
+fn options_first_nested_commands() {
+    #[derive(Debug, Clone, PartialEq)]
+    enum Cmd {
+        Run(bool, Vec<String>),
+        Deploy(bool),
+    }
+    let verbose = short('v').switch();
+    let args = positional::<String>("ARG").many();
+    let run = construct!(Cmd::Run(verbose, args))
+        .to_options()
+        .command("run");
+    let verbose = short('v').switch();
+    let deploy = construct!(Cmd::Deploy(verbose))
+        .to_options()
+        .command("staging")
+        .to_options()
+        .command("deploy");
+    let parser = construct!([run, deploy]).to_options().options_first();
+
+    // "staging" is a command only inside of "deploy"
+    let r = parser.run_inner(&["run", "staging", "-v"]).unwrap();
+    assert_eq!(
+        r,
+        Cmd::Run(false, vec!["staging".to_owned(), "-v".to_owned()])
+    );
+
+    let r = parser.run_inner(&["deploy", "staging", "-v"]).unwrap();
+    assert_eq!(r, Cmd::Deploy(true));
+}
+
+#[test]
+fn options_first_relaxed_commands() {
+    fn parser() -> OptionParser<bool> {
+        let verbose = short('v').switch();
+        let build = verbose.to_options().command("build");
+        construct!([build]).to_options().options_first()
+    }
+
+    let r = parser().abbreviations().run_inner(&["bui", "-v"]).unwrap();
+    assert!(r);
+
+    let r = parser().ignore_case().run_inner(&["BUILD", "-v"]).unwrap();
+    assert!(r);
+
+    let r = parser()
+        .underscores_as_dashes()
+        .run_inner(&["build", "-v"])
+        .unwrap();
+    assert!(r);
+}
+
+#[test]
+fn options_first_hidden_aliases() {
+    let output = long("output")
+        .long("out")
+        .argument::<String>("FILE")
+        .optional();
+    let verbose = short('v').switch();
+    let rest = positional::<String>("REST").many();
+    let parser = construct!(output, verbose, rest)
+        .to_options()
+        .options_first();
+
+    let r = parser.run_inner(&["--out", "x", "-v"]).unwrap();
+    assert_eq!(r, (Some("x".to_owned()), true, Vec::new()));
+}
+
+#[test]
+fn pass_through_unknown() {
+    use std::ffi::OsString;
+    fn os(items: &[&str]) -> Vec<OsString> {
+        items.iter().map(OsString::from).collect()
+    }
+    let release = long("release").help("Build in release mode").switch();
+    let jobs = short('j').argument::<usize>("N").fallback(1);
+    let rest = pass_through().help("Compiler flags");
+    let parser = construct!(release, jobs, rest).to_options();
+
+    let r = parser
+        .run_inner(&[
+            "-O2",
+            "-j4",
+            "--target=x86",
+            "--release",
+            "-Wall",
+            "-I",
+            "inc",
+        ])
+        .unwrap();
+    assert_eq!(
+        r,
+        (true, 4, os(&["-O2", "--target=x86", "-Wall", "-I", "inc"]))
+    );
+
+    let r = parser.run_inner(&["--release", "--", "--release"]).unwrap();
+    assert_eq!(r, (true, 1, os(&["--release"])));
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [--release] [-j=N] [ARGS passed through]
+
+Available options:
+        --release  Build in release mode
+    -j=N
+    ARGS           Compiler flags
+    -h, --help     Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn pass_through_placement() {
+    use std::ffi::OsString;
+    let release = long("release").switch();
+    let rest = pass_through().after_known();
+    let parser = construct!(release, rest).to_options();
+
+    let r = parser.run_inner(&["--release", "-O2", "-g"]).unwrap();
+    assert_eq!(r, (true, vec![OsString::from("-O2"), OsString::from("-g")]));
+
+    let r = parser
+        .run_inner(&["-O2", "--release"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`-O2` is not expected in this context");
+
+    let release = long("release").switch();
+    let rest = pass_through().before_known();
+    let parser = construct!(release, rest).to_options();
+
+    let r = parser.run_inner(&["-O2", "--release"]).unwrap();
+    assert_eq!(r, (true, vec![OsString::from("-O2")]));
+
+    let r = parser
+        .run_inner(&["--release", "-O2"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "`-O2` is not expected in this context");
+}
+
+#[test]
+fn global_options_in_subcommands() {
+    let release = long("release").help("Build in release mode").switch();
+    let build = construct!(release)
+        .to_options()
+        .descr("Build the project")
+        .command("build");
+    let verbose = short('v')
+        .long("verbose")
+        .help("Be verbose")
+        .global()
+        .switch();
+    let config = long("config")
+        .help("Config file")
+        .global()
+        .argument::<String>("FILE")
+        .optional();
+    let parser = construct!(verbose, config, build).to_options();
+
+    let r = parser
+        .run_inner(&["build", "--config", "a.toml", "--release", "-v"])
+        .unwrap();
+    assert_eq!(r, (true, Some("a.toml".to_owned()), true));
+
+    let r = parser.run_inner(&["-v", "build"]).unwrap();
+    assert_eq!(r, (true, None, false));
+
+    let r = parser
+        .run_inner(&["build", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Build the project
+
+Usage: build [--release]
+
+Global options:
+    -v, --verbose      Be verbose
+        --config=FILE  Config file
+
+Available options:
+        --release      Build in release mode
+    -h, --help         Prints help information
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn global_options_after_command() {
+    let release = long("release").switch();
+    let build = construct!(release).to_options().command("build");
+    let verbose = short('v').global().switch();
+    // global items are consumed by the parent even if the command goes first
+    let parser = construct!(build, verbose).to_options();
+
+    let r = parser.run_inner(&["build", "-v", "--release"]).unwrap();
+    assert_eq!(r, (true, true));
+
+    let r = parser
+        .run_inner(&["build", "-v", "-v"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "argument `-v` cannot be used multiple times in this context"
+    );
+}
+
+#[test]
+fn help_subcommand() {
+    let force = long("force").help("Deploy even if checks fail").switch();
+    let deploy = construct!(force)
+        .to_options()
+        .descr("Deploy the build")
+        .command("deploy")
+        .help("Deploy the build");
+    let build = construct!(deploy)
+        .to_options()
+        .descr("Build the project")
+        .command("build")
+        .short('b')
+        .help("Build the project");
+    let parser = build.to_options().help_command();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: COMMAND ...
+
+Available options:
+    -h, --help  Prints help information
+
+Available commands:
+    build, b    Build the project
+    help        Prints help for a subcommand
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["help", "b", "deploy"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Deploy the build
+
+Usage: build deploy [--force]
+
+Available options:
+        --force  Deploy even if checks fail
+    -h, --help   Prints help information
+";
+    assert_eq!(r, expected);
+
+    let r = parser.run_inner(&["help"]).unwrap_err().unwrap_stdout();
+    assert!(r.starts_with("Usage: COMMAND ..."));
+
+    let r = parser
+        .run_inner(&["help", "build", "deplyo"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(
+        r,
+        "no such command or positional: `deplyo`, did you mean `deploy`?"
+    );
+}
+
+#[test]
+fn help_subcommand_relaxed_names() {
+    fn parser() -> OptionParser<bool> {
+        let force = long("force").help("Deploy even if checks fail").switch();
+        let deploy = construct!(force).to_options().command("deploy");
+        let build = construct!(deploy).to_options().command("build");
+        build.to_options().help_command()
+    }
+    let expected = "\
+Usage: build deploy [--force]
+
+Available options:
+        --force  Deploy even if checks fail
+    -h, --help   Prints help information
+";
+
+    let r = parser()
+        .abbreviations()
+        .run_inner(&["help", "bui", "dep"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, expected);
+
+    let r = parser()
+        .ignore_case()
+        .run_inner(&["help", "BUILD", "Deploy"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn help_search_keyword() {
+    let retries = long("retries")
+        .help("Number of times to retry a failed upload")
+        .argument::<u32>("N")
+        .optional();
+    let force = long("force").help("Deploy even if checks fail").switch();
+    let deploy = construct!(force, retries)
+        .map(|_| ())
+        .to_options()
+        .command("deploy")
+        .help("Deploy the build");
+    let retry = long("retry").help("Retry failed downloads").switch();
+    let fetch = construct!(retry)
+        .map(|_| ())
+        .to_options()
+        .command("fetch")
+        .help("Fetch dependencies");
+    let verbose = short('v').long("verbose").help("Verbose output").switch();
+    let cmd = construct!([deploy, fetch]);
+    let parser = construct!(verbose, cmd)
+        .to_options()
+        .help_search()
+        .help_command();
+
+    let r = parser
+        .run_inner(&["--help", "RETRY"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+deploy:
+        --retries=N  Number of times to retry a failed upload
+
+fetch:
+        --retry      Retry failed downloads
+";
+    assert_eq!(r, expected);
+
+    // typos in names and help are tolerated, matching commands are listed too
+    let r = parser
+        .run_inner(&["help", "--search", "depoly"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Top level:
+    deploy       Deploy the build
+
+deploy:
+        --force  Deploy even if checks fail
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--help", "verbose"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Top level:
+    -v, --verbose  Verbose output
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["--help", "cache"])
+        .unwrap_err()
+        .unwrap_stderr();
+    assert_eq!(r, "no help items match `cache`");
+
+    // without a keyword help works as usual
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert!(r.starts_with("Usage: [-v] COMMAND ..."));
+}
+
+#[test]
+fn help_search_keyword_headings() {
+    let host = long("host")
+        .help("Server to connect to")
+        .argument::<String>("HOST");
+    let port = long("port").help("Port to use").argument::<u16>("PORT");
+    let connection = construct!(host, port).heading("Connection");
+    let deploy = pure(())
+        .to_options()
+        .command("deploy")
+        .help("Deploy to a host")
+        .heading("Commands that need a server");
+    let parser = construct!(connection, deploy)
+        .map(|_| ())
+        .to_options()
+        .help_search();
+
+    let r = parser
+        .run_inner(&["--help", "host"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Top level:
+        --host=HOST  Server to connect to
+    deploy           Deploy to a host
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn usage_examples() {
+    let release = long("release").help("Build in release mode").switch();
+    let build = construct!(release)
+        .to_options()
+        .example("--release", "Build in release mode")
+        .command("build");
+    let verbose = short('v').help("Verbose output").switch();
+    let parser = construct!(verbose, build)
+        .to_options()
+        .example("-v build", "Build the project with verbose output");
+
+    // examples are listed in detailed help only
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    assert!(!r.contains("Examples"));
+
+    let r = parser
+        .run_inner(&["--help", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    let expected = "\
+Usage: [-v] COMMAND ...
+
+Available options:
+    -v          Verbose output
+    -h, --help  Prints help information
+
+Available commands:
+    build
+
+Examples:
+    -v build    Build the project with verbose output
+";
+    assert_eq!(r, expected);
+
+    let r = parser
+        .run_inner(&["build", "--help", "--help"])
+        .unwrap_err()
+        .unwrap_stdout();
+    assert!(r.ends_with("Examples:\n    build --release  Build in release mode\n"));
+
+    parser.check_examples();
+}
+
+#[test]
+#[should_panic(expected = "-v build --debug: `--debug` is not expected in this context")]
+fn usage_examples_check() {
+    let build = pure(()).to_options().command("build");
+    let verbose = short('v').switch();
+    construct!(verbose, build)
+        .to_options()
+        .example("-v build", "Build the project")
+        .example("-v build --debug", "Build the project in debug mode")
+        .check_examples();
+}
+
+#[test]
+fn usage_examples_quoted() {
+    let name = long("name").argument::<String>("NAME").guard(
+        |n| n.contains(' ') || n == "\\",
+        "expected a name with spaces",
+    );
+    let greet = construct!(name).to_options().command("greet");
+    construct!(greet)
+        .to_options()
+        .example(r#"greet --name "John Smith""#, "Greet John")
+        .example("greet --name 'Jane  Doe'", "Greet Jane")
+        .example(r"greet --name Mr\ Smith", "Greet Mr Smith")
+        .example(r#"greet --name "say \"hi\"" "#, "Quotes inside of quotes")
+        .example(r#"greet --name "\\""#, "Backslash")
+        .check_examples();
+}
+
+#[test]
+#[should_panic(expected = "--name 'John Smith: unterminated single quote")]
+fn usage_examples_unterminated_quote() {
+    let name = long("name").argument::<String>("NAME");
+    construct!(name)
+        .to_options()
+        .example("--name 'John Smith", "Greet John")
+        .check_examples();
+}
+
+#[test]
+fn rich_blocks_in_help() {
+    let mut footer = Doc::default();
+    footer.text("Supported formats:");
+    footer.bullet_list(["json", "yaml"]);
+    footer.text("Sample config:");
+    footer.code_block("[server]\nport = 8080");
+    footer.link("Manual", "https://example.com/manual");
+    let parser = short('v')
+        .help("Verbose output")
+        .switch()
+        .to_options()
+        .footer(footer);
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: [-v]
+
+Available options:
+    -v          Verbose output
+    -h, --help  Prints help information
+
+Supported formats:
+  - json
+  - yaml
+Sample config:
+
+    [server]
+    port = 8080
+
+Manual <https://example.com/manual>
+";
+    assert_eq!(r, expected);
+}
+
+#[test]
+fn nested_doc_keeps_item_margin() {
+    let mut help = Doc::default();
+    help.doc(&"Output format, one of:".into());
+    help.bullet_list(["json", "yaml"]);
+    help.text("\n\nFor example:");
+    help.code_block("app -f json");
+    let parser = short('f').help(help).argument::<String>("FMT").to_options();
+
+    let r = parser.run_inner(&["--help"]).unwrap_err().unwrap_stdout();
+    let expected = "\
+Usage: -f=FMT
+
+Available options:
+    -f=FMT      Output format, one of:
+                  - json
+                  - yaml
+    -h, --help  Prints help information
+";
+    assert_eq!(r, expected);
+}